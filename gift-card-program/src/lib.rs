@@ -6,13 +6,16 @@ use solana_program::{
     msg,
     program_error::ProgramError,
     pubkey::Pubkey,
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     program::{invoke, invoke_signed},
+    program_pack::Pack,
     system_instruction,
+    system_program,
     sysvar::{rent::Rent, Sysvar},
     clock::Clock,
 };
 use thiserror::Error;
-use spl_token::{instruction as token_instruction, id as token_program_id};
+use spl_token::{instruction as token_instruction, id as token_program_id, state::Account as TokenAccount};
 
 /// Error types for the Gift Card program
 #[derive(Error, Debug, Copy, Clone)]
@@ -37,6 +40,51 @@ pub enum GiftCardError {
     
     #[error("Invalid token account")]
     InvalidTokenAccount,
+    
+    #[error("Invalid program config")]
+    InvalidConfig,
+    
+    #[error("Amount below the configured minimum")]
+    AmountTooSmall,
+    
+    #[error("Amount above the configured maximum")]
+    AmountTooLarge,
+    
+    #[error("Message too long")]
+    MessageTooLong,
+    
+    #[error("Token mint not allowed")]
+    MintNotAllowed,
+    
+    #[error("Invalid protocol fee")]
+    InvalidFee,
+    
+    #[error("Gift card creation is paused")]
+    CreationPaused,
+    
+    #[error("Gift card redemption is paused")]
+    RedemptionPaused,
+    
+    #[error("Staking is paused")]
+    StakingPaused,
+    
+    #[error("Invalid mint registry entry")]
+    InvalidMintEntry,
+    
+    #[error("Outstanding liability cap exceeded for this mint")]
+    LiabilityCapExceeded,
+    
+    #[error("Metadata URI too long")]
+    UriTooLong,
+    
+    #[error("Invalid redemption destination")]
+    InvalidDestination,
+    
+    #[error("Redemption cannot be paused again until the cooldown ends")]
+    RedemptionPauseCooldown,
+    
+    #[error("Gift card has not expired")]
+    NotExpired,
 }
 
 impl From<GiftCardError> for ProgramError {
@@ -54,9 +102,12 @@ pub enum GiftCardInstruction {
     /// 0. `[signer]` The account funding the gift card
     /// 1. `[writable]` The gift card account (PDA)
     /// 2. `[]` System program
-    /// 3. `[]` Token program (optional, for SPL tokens)
-    /// 4. `[writable]` Source token account (optional, for SPL tokens)
-    /// 5. `[writable]` Destination token account (optional, for SPL tokens)
+    /// 3. `[]` The program config account (PDA)
+    /// 4. `[writable]` Fee recipient (its token account for SPL tokens)
+    /// 5. `[]` Token program (optional, for SPL tokens)
+    /// 6. `[writable]` Source token account (optional, for SPL tokens)
+    /// 7. `[writable]` Destination token account (optional, for SPL tokens)
+    /// 8. `[writable]` Mint registry entry (optional, for SPL tokens)
     CreateGiftCard {
         amount: u64,
         recipient_pubkey: Pubkey,
@@ -64,16 +115,38 @@ pub enum GiftCardInstruction {
         message: String,
         token_mint: Option<Pubkey>, // New field for token mint
         theme_id: u8,
+        metadata_uri: Option<String>,
+        content_hash: Option<[u8; 32]>,
     },
     
-    /// Redeem a gift card
+    /// Redeem a gift card, optionally paying out to a destination other than the recipient
     /// 
     /// Accounts expected:
     /// 0. `[signer]` The gift card recipient
     /// 1. `[writable]` The gift card account (PDA)
-    /// 2. `[]` Token program (optional, for SPL tokens)
-    /// 3. `[writable]` Recipient's token account (optional, for SPL tokens)
-    RedeemGiftCard {},
+    /// 2. `[]` The program config account (PDA)
+    /// 3. `[writable]` Destination system account (optional, for SOL with a destination)
+    ///
+    /// For SPL token gift cards, accounts 3.. are instead:
+    /// 3. `[]` Token program
+    /// 4. `[writable]` Destination token account (owned by the recipient unless a destination is given)
+    /// 5. `[writable]` Mint registry entry
+    RedeemGiftCard {
+        destination: Option<Pubkey>,
+    },
+    
+    /// Return an expired, unredeemed gift card's value to its creator
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The gift card creator
+    /// 1. `[writable]` The gift card account (PDA)
+    /// 2. `[]` The program config account (PDA)
+    ///
+    /// For SPL token gift cards, additionally:
+    /// 3. `[]` Token program
+    /// 4. `[writable]` The creator's token account for the card's mint
+    /// 5. `[writable]` Mint registry entry
+    ReclaimGiftCard {},
     
     /// Add funds to the staking pool
     /// 
@@ -81,6 +154,7 @@ pub enum GiftCardInstruction {
     /// 0. `[signer]` The staker
     /// 1. `[writable]` The staking pool account (PDA)
     /// 2. `[]` System program
+    /// 3. `[]` The program config account (PDA)
     Stake {
         amount: u64,
     },
@@ -90,8 +164,112 @@ pub enum GiftCardInstruction {
     /// Accounts expected:
     /// 0. `[signer]` The program admin
     /// 1. `[writable]` The staking pool account (PDA)
-    /// 2+ `[writable]` Variable number of staker accounts to receive rewards
+    /// 2. `[]` The program config account (PDA)
+    /// 3+ `[writable]` Variable number of staker accounts to receive rewards
     DistributeRewards {},
+    
+    /// Create the global program config (PDA seeded by `b"config"`)
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The program's upgrade authority, becoming the initial admin and funding the config account
+    /// 1. `[writable]` The program config account (PDA)
+    /// 2. `[]` System program
+    /// 3. `[]` This program's ProgramData account
+    InitializeConfig {
+        min_card_amount: u64,
+        max_card_amount: u64,
+        max_message_len: u32,
+        protocol_fee_bps: u16,
+        fee_recipient: Pubkey,
+    },
+    
+    /// Replace the program config settings
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` The config admin
+    /// 1. `[writable]` The program config account (PDA)
+    UpdateConfig {
+        min_card_amount: u64,
+        max_card_amount: u64,
+        max_message_len: u32,
+        protocol_fee_bps: u16,
+        fee_recipient: Pubkey,
+    },
+    
+    /// Nominate a new admin; takes effect once they call AcceptAdmin
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` The config admin
+    /// 1. `[writable]` The program config account (PDA)
+    TransferAdmin {
+        new_admin: Pubkey,
+    },
+    
+    /// Accept a pending admin nomination
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` The pending admin
+    /// 1. `[writable]` The program config account (PDA)
+    AcceptAdmin {},
+    
+    /// Set the emergency pause bits (see the `PAUSE_*` constants)
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` The pauser or the config admin
+    /// 1. `[writable]` The program config account (PDA)
+    SetPauseFlags {
+        flags: u8,
+    },
+    
+    /// Designate the account allowed to flip the pause bits
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` The config admin
+    /// 1. `[writable]` The program config account (PDA)
+    SetPauser {
+        pauser: Pubkey,
+    },
+    
+    /// Replace a card's off-chain metadata URI and content hash until it is redeemed
+    /// (also moves legacy cards to the current layout)
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The gift card creator, funding any extra rent
+    /// 1. `[writable]` The gift card account (PDA)
+    /// 2. `[]` System program
+    UpdateCardMetadata {
+        metadata_uri: Option<String>,
+        content_hash: Option<[u8; 32]>,
+    },
+    
+    /// Register an SPL mint so it can back gift cards
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer, writable]` The config admin, funding the entry
+    /// 1. `[]` The program config account (PDA)
+    /// 2. `[writable]` The mint registry entry (PDA seeded by `b"mint"` and the mint)
+    /// 3. `[]` The SPL mint
+    /// 4. `[]` System program
+    RegisterMint {
+        min_card_amount: u64,
+        max_card_amount: u64,
+        per_card_cap: u64,
+        liability_cap: u64,
+    },
+    
+    /// Update the limits of a registered mint
+    /// 
+    /// Accounts expected:
+    /// 0. `[signer]` The config admin
+    /// 1. `[]` The program config account (PDA)
+    /// 2. `[writable]` The mint registry entry (PDA)
+    UpdateMint {
+        enabled: bool,
+        min_card_amount: u64,
+        max_card_amount: u64,
+        per_card_cap: u64,
+        liability_cap: u64,
+    },
 }
 
 /// Gift card state stored in account data
@@ -105,6 +283,231 @@ pub struct GiftCard {
     pub message: String,
     pub token_mint: Option<Pubkey>, // New field for token mint
     pub theme_id: u8,
+    pub metadata_uri: Option<String>,  // Off-chain artwork, video or letter
+    pub content_hash: Option<[u8; 32]>,  // SHA-256 of the content at metadata_uri
+}
+
+/// Gift card layout from before `metadata_uri` and `content_hash` were added
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct LegacyGiftCard {
+    pub creator: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub is_redeemed: bool,
+    pub expiry_time: u64,
+    pub message: String,
+    pub token_mint: Option<Pubkey>,
+    pub theme_id: u8,
+}
+
+impl From<LegacyGiftCard> for GiftCard {
+    fn from(card: LegacyGiftCard) -> Self {
+        GiftCard {
+            creator: card.creator,
+            recipient: card.recipient,
+            amount: card.amount,
+            is_redeemed: card.is_redeemed,
+            expiry_time: card.expiry_time,
+            message: card.message,
+            token_mint: card.token_mint,
+            theme_id: card.theme_id,
+            metadata_uri: None,
+            content_hash: None,
+        }
+    }
+}
+
+impl GiftCard {
+    /// Read a card, falling back to the legacy layout for accounts sized before the metadata fields
+    pub fn unpack(data: &[u8]) -> Result<Self, ProgramError> {
+        match GiftCard::deserialize(&mut &data[..]) {
+            Ok(gift_card) => Ok(gift_card),
+            Err(_) => Ok(LegacyGiftCard::deserialize(&mut &data[..])?.into()),
+        }
+    }
+    
+    /// Write a card, keeping the legacy layout when the account is too small for the current one
+    pub fn pack(&self, data: &mut [u8]) -> ProgramResult {
+        let bytes = self.try_to_vec()?;
+        if bytes.len() <= data.len() {
+            data[..bytes.len()].copy_from_slice(&bytes);
+            return Ok(());
+        }
+        if self.metadata_uri.is_some() || self.content_hash.is_some() {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        
+        let legacy = LegacyGiftCard {
+            creator: self.creator,
+            recipient: self.recipient,
+            amount: self.amount,
+            is_redeemed: self.is_redeemed,
+            expiry_time: self.expiry_time,
+            message: self.message.clone(),
+            token_mint: self.token_mint,
+            theme_id: self.theme_id,
+        };
+        legacy.serialize(&mut &mut data[..])?;
+        Ok(())
+    }
+    
+    /// Account size for a card with the given message and metadata URI
+    pub fn space(message: &str, metadata_uri: &Option<String>) -> usize {
+        let uri_len = metadata_uri.as_ref().map_or(0, |uri| uri.len());
+        32 + 32 + 8 + 1 + 8 + message.len() + 4 + 1 + 32 + 1 + (1 + 4 + uri_len) + (1 + 32)
+    }
+    
+    /// Card value held as lamports in the card account itself (SOL cards only)
+    pub fn held_lamports(&self) -> u64 {
+        if self.token_mint.is_none() { self.amount } else { 0 }
+    }
+}
+
+/// Seed for the program config PDA
+pub const CONFIG_SEED: &[u8] = b"config";
+
+/// Longest off-chain metadata URI a card can reference
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+/// Seed for mint registry entry PDAs
+pub const MINT_ENTRY_SEED: &[u8] = b"mint";
+
+/// Basis point denominator (10000 = 100%)
+pub const BPS_DENOMINATOR: u64 = 10000;
+
+/// Pause bit blocking CreateGiftCard
+pub const PAUSE_CREATION: u8 = 1 << 0;
+
+/// Pause bit blocking RedeemGiftCard
+pub const PAUSE_REDEMPTION: u8 = 1 << 1;
+
+/// Pause bit blocking Stake and DistributeRewards
+pub const PAUSE_STAKING: u8 = 1 << 2;
+
+/// Longest a redemption pause stays in force (7 days), so recipients can't be locked out
+pub const MAX_REDEMPTION_PAUSE: i64 = 7 * 24 * 60 * 60;
+
+/// Time after a redemption pause window ends before a new one can start (7 days)
+pub const REDEMPTION_PAUSE_COOLDOWN: i64 = 7 * 24 * 60 * 60;
+
+/// Global program configuration stored in the config PDA
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct ProgramConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>,
+    pub min_card_amount: u64,
+    pub max_card_amount: u64,
+    pub max_message_len: u32,
+    pub protocol_fee_bps: u16,
+    pub fee_recipient: Pubkey,
+    pub pauser: Pubkey,
+    pub pause_flags: u8,
+    pub redemption_paused_at: i64,  // Unix timestamp the redemption bit was set
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    /// Account size
+    pub const LEN: usize = 32 + (1 + 32) + 8 + 8 + 4 + 2 + 32 + 32 + 1 + 8 + 1;
+    
+    /// Whether the given pause bit is currently in force
+    pub fn is_paused(&self, flag: u8, now: i64) -> bool {
+        if self.pause_flags & flag == 0 {
+            return false;
+        }
+        if flag == PAUSE_REDEMPTION {
+            return now < self.redemption_paused_at.saturating_add(MAX_REDEMPTION_PAUSE);
+        }
+        true
+    }
+    
+    /// Replace the pause bits; re-raising redemption inside the current window resumes it,
+    /// and a new window can't open until the cooldown after the last one has passed
+    pub fn set_pause_flags(&mut self, flags: u8, now: i64) -> ProgramResult {
+        if flags & PAUSE_REDEMPTION != 0 && self.pause_flags & PAUSE_REDEMPTION == 0 {
+            let window_end = self.redemption_paused_at.saturating_add(MAX_REDEMPTION_PAUSE);
+            if self.redemption_paused_at == 0 || now >= window_end.saturating_add(REDEMPTION_PAUSE_COOLDOWN) {
+                self.redemption_paused_at = now;
+            } else if now >= window_end {
+                return Err(GiftCardError::RedemptionPauseCooldown.into());
+            }
+        }
+        self.pause_flags = flags;
+        Ok(())
+    }
+    
+    /// Check that the settings are internally consistent
+    pub fn validate(&self) -> ProgramResult {
+        if self.min_card_amount == 0 || self.min_card_amount > self.max_card_amount {
+            return Err(GiftCardError::InvalidConfig.into());
+        }
+        if self.protocol_fee_bps as u64 > BPS_DENOMINATOR {
+            return Err(GiftCardError::InvalidFee.into());
+        }
+        Ok(())
+    }
+    
+    /// Protocol fee owed on a gross card amount
+    pub fn fee_for(&self, amount: u64) -> Result<u64, ProgramError> {
+        let fee = (amount as u128)
+            .checked_mul(self.protocol_fee_bps as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        Ok(fee as u64)
+    }
+}
+
+/// Per-mint registry entry controlling SPL token gift cards
+#[derive(BorshSerialize, BorshDeserialize, Clone, Debug, PartialEq)]
+pub struct MintEntry {
+    pub mint: Pubkey,
+    pub enabled: bool,
+    pub min_card_amount: u64,
+    pub max_card_amount: u64,
+    pub per_card_cap: u64,   // Max value a single card may hold after fees
+    pub liability_cap: u64,  // Max total value of unredeemed cards in this mint
+    pub outstanding: u64,    // Current total value of unredeemed cards
+    pub bump: u8,
+}
+
+impl MintEntry {
+    /// Account size
+    pub const LEN: usize = 32 + 1 + 8 + 8 + 8 + 8 + 8 + 1;
+    
+    /// Check that the limits are internally consistent
+    pub fn validate(&self) -> ProgramResult {
+        if self.min_card_amount == 0 || self.min_card_amount > self.max_card_amount {
+            return Err(GiftCardError::InvalidMintEntry.into());
+        }
+        Ok(())
+    }
+    
+    /// Check a new card against this mint's limits and add it to the outstanding total
+    pub fn record_issuance(&mut self, amount: u64, card_amount: u64) -> ProgramResult {
+        if !self.enabled {
+            return Err(GiftCardError::MintNotAllowed.into());
+        }
+        if amount < self.min_card_amount {
+            return Err(GiftCardError::AmountTooSmall.into());
+        }
+        if amount > self.max_card_amount || card_amount > self.per_card_cap {
+            return Err(GiftCardError::AmountTooLarge.into());
+        }
+        
+        let outstanding = self.outstanding.checked_add(card_amount).ok_or(ProgramError::ArithmeticOverflow)?;
+        if outstanding > self.liability_cap {
+            return Err(GiftCardError::LiabilityCapExceeded.into());
+        }
+        self.outstanding = outstanding;
+        
+        Ok(())
+    }
+    
+    /// Remove a redeemed or reclaimed card from the outstanding total
+    pub fn record_redemption(&mut self, card_amount: u64) -> ProgramResult {
+        self.outstanding = self.outstanding.checked_sub(card_amount).ok_or(ProgramError::ArithmeticOverflow)?;
+        Ok(())
+    }
 }
 
 /// Staking pool state stored in account data
@@ -134,12 +537,17 @@ pub fn process_instruction(
             expiry_time, 
             message, 
             token_mint,
-            theme_id 
+            theme_id,
+            metadata_uri,
+            content_hash,
         } => {
-            process_create_gift_card(program_id, accounts, amount, recipient_pubkey, expiry_time, message, token_mint, theme_id)
+            process_create_gift_card(program_id, accounts, amount, recipient_pubkey, expiry_time, message, token_mint, theme_id, metadata_uri, content_hash)
+        },
+        GiftCardInstruction::RedeemGiftCard { destination } => {
+            process_redeem_gift_card(program_id, accounts, destination)
         },
-        GiftCardInstruction::RedeemGiftCard {} => {
-            process_redeem_gift_card(program_id, accounts)
+        GiftCardInstruction::ReclaimGiftCard {} => {
+            process_reclaim_gift_card(program_id, accounts)
         },
         GiftCardInstruction::Stake { amount } => {
             process_stake(program_id, accounts, amount)
@@ -147,6 +555,56 @@ pub fn process_instruction(
         GiftCardInstruction::DistributeRewards {} => {
             process_distribute_rewards(program_id, accounts)
         },
+        GiftCardInstruction::InitializeConfig {
+            min_card_amount,
+            max_card_amount,
+            max_message_len,
+            protocol_fee_bps,
+            fee_recipient,
+        } => {
+            process_initialize_config(program_id, accounts, min_card_amount, max_card_amount, max_message_len, protocol_fee_bps, fee_recipient)
+        },
+        GiftCardInstruction::UpdateConfig {
+            min_card_amount,
+            max_card_amount,
+            max_message_len,
+            protocol_fee_bps,
+            fee_recipient,
+        } => {
+            process_update_config(program_id, accounts, min_card_amount, max_card_amount, max_message_len, protocol_fee_bps, fee_recipient)
+        },
+        GiftCardInstruction::TransferAdmin { new_admin } => {
+            process_transfer_admin(program_id, accounts, new_admin)
+        },
+        GiftCardInstruction::AcceptAdmin {} => {
+            process_accept_admin(program_id, accounts)
+        },
+        GiftCardInstruction::SetPauseFlags { flags } => {
+            process_set_pause_flags(program_id, accounts, flags)
+        },
+        GiftCardInstruction::SetPauser { pauser } => {
+            process_set_pauser(program_id, accounts, pauser)
+        },
+        GiftCardInstruction::UpdateCardMetadata { metadata_uri, content_hash } => {
+            process_update_card_metadata(program_id, accounts, metadata_uri, content_hash)
+        },
+        GiftCardInstruction::RegisterMint {
+            min_card_amount,
+            max_card_amount,
+            per_card_cap,
+            liability_cap,
+        } => {
+            process_register_mint(program_id, accounts, min_card_amount, max_card_amount, per_card_cap, liability_cap)
+        },
+        GiftCardInstruction::UpdateMint {
+            enabled,
+            min_card_amount,
+            max_card_amount,
+            per_card_cap,
+            liability_cap,
+        } => {
+            process_update_mint(program_id, accounts, enabled, min_card_amount, max_card_amount, per_card_cap, liability_cap)
+        },
     }
 }
 
@@ -160,22 +618,52 @@ pub fn process_create_gift_card(
     message: String,
     token_mint: Option<Pubkey>,
     theme_id: u8,
+    metadata_uri: Option<String>,
+    content_hash: Option<[u8; 32]>,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     
     let creator = next_account_info(accounts_iter)?;
     let gift_card_account = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let fee_account = next_account_info(accounts_iter)?;
     
     // Verify the creator signed the transaction
     if !creator.is_signer {
         return Err(GiftCardError::NotAuthorized.into());
     }
     
+    // Enforce the program config limits
+    let config = load_config(program_id, config_account)?;
+    if config.is_paused(PAUSE_CREATION, Clock::get()?.unix_timestamp) {
+        return Err(GiftCardError::CreationPaused.into());
+    }
+    if message.len() > config.max_message_len as usize {
+        return Err(GiftCardError::MessageTooLong.into());
+    }
+    if metadata_uri.as_ref().is_some_and(|uri| uri.len() > MAX_METADATA_URI_LEN) {
+        return Err(GiftCardError::UriTooLong.into());
+    }
+    
+    // SOL cards are bounded by the config; token cards by their mint registry entry
+    if token_mint.is_none() {
+        if amount < config.min_card_amount {
+            return Err(GiftCardError::AmountTooSmall.into());
+        }
+        if amount > config.max_card_amount {
+            return Err(GiftCardError::AmountTooLarge.into());
+        }
+    }
+    
+    // The protocol fee is deducted from the amount sent by the creator
+    let fee = config.fee_for(amount)?;
+    let card_amount = amount.checked_sub(fee).ok_or(ProgramError::ArithmeticOverflow)?;
+    
     // Verify the gift card account is owned by the program
     if gift_card_account.owner != program_id {
         // If the account doesn't exist yet, create it
-        let space = 32 + 32 + 8 + 1 + 8 + message.len() as usize + 4 + 1 + 32 + 1;  // Size of GiftCard struct with token_mint
+        let space = GiftCard::space(&message, &metadata_uri);
         let rent = Rent::get()?;
         let lamports = rent.minimum_balance(space);
         
@@ -198,12 +686,18 @@ pub fn process_create_gift_card(
         let token_program = next_account_info(accounts_iter)?;
         let source_token_account = next_account_info(accounts_iter)?;
         let destination_token_account = next_account_info(accounts_iter)?;
+        let mint_entry_account = next_account_info(accounts_iter)?;
         
         // Verify token program ID
         if token_program.key != &token_program_id::ID {
             return Err(GiftCardError::InvalidTokenAccount.into());
         }
         
+        // Enforce the mint's registry limits and track its outstanding liability
+        let mut mint_entry = load_mint_entry(program_id, mint_entry_account, &mint)?;
+        mint_entry.record_issuance(amount, card_amount)?;
+        mint_entry.serialize(&mut *mint_entry_account.data.borrow_mut())?;
+        
         // Transfer tokens from source to destination
         invoke(
             &token_instruction::transfer(
@@ -212,7 +706,7 @@ pub fn process_create_gift_card(
                 destination_token_account.key,
                 creator.key,
                 &[creator.key],
-                amount,
+                card_amount,
             )?,
             &[
                 source_token_account.clone(),
@@ -223,33 +717,75 @@ pub fn process_create_gift_card(
         )?;
         
         msg!("SPL token transferred to gift card token account");
+        
+        if fee > 0 {
+            // The fee must land in the fee recipient's account for this mint
+            let fee_token_account = TokenAccount::unpack(&fee_account.data.borrow())?;
+            if fee_token_account.owner != config.fee_recipient || fee_token_account.mint != mint {
+                return Err(GiftCardError::InvalidTokenAccount.into());
+            }
+            
+            invoke(
+                &token_instruction::transfer(
+                    token_program.key,
+                    source_token_account.key,
+                    fee_account.key,
+                    creator.key,
+                    &[creator.key],
+                    fee,
+                )?,
+                &[
+                    source_token_account.clone(),
+                    fee_account.clone(),
+                    creator.clone(),
+                    token_program.clone(),
+                ],
+            )?;
+        }
     } else {
         // This is a SOL gift card - transfer native SOL
         invoke(
             &system_instruction::transfer(
                 creator.key,
                 gift_card_account.key,
-                amount,
+                card_amount,
             ),
             &[creator.clone(), gift_card_account.clone(), system_program.clone()],
         )?;
         
         msg!("SOL transferred to gift card account");
+        
+        if fee > 0 {
+            if *fee_account.key != config.fee_recipient {
+                return Err(GiftCardError::InvalidConfig.into());
+            }
+            
+            invoke(
+                &system_instruction::transfer(
+                    creator.key,
+                    fee_account.key,
+                    fee,
+                ),
+                &[creator.clone(), fee_account.clone(), system_program.clone()],
+            )?;
+        }
     }
     
     // Initialize gift card data
     let gift_card = GiftCard {
         creator: *creator.key,
         recipient: recipient_pubkey,
-        amount,
+        amount: card_amount,
         is_redeemed: false,
         expiry_time,
         message,
         token_mint,
         theme_id,
+        metadata_uri,
+        content_hash,
     };
     
-    gift_card.serialize(&mut *gift_card_account.data.borrow_mut())?;
+    gift_card.pack(&mut gift_card_account.data.borrow_mut())?;
     
     msg!("Gift card created successfully!");
     
@@ -260,24 +796,32 @@ pub fn process_create_gift_card(
 pub fn process_redeem_gift_card(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
+    destination: Option<Pubkey>,
 ) -> ProgramResult {
     let accounts_iter = &mut accounts.iter();
     
     let recipient = next_account_info(accounts_iter)?;
     let gift_card_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
     
     // Verify the recipient signed the transaction
     if !recipient.is_signer {
         return Err(GiftCardError::NotAuthorized.into());
     }
     
+    let config = load_config(program_id, config_account)?;
+    let clock = Clock::get()?;
+    if config.is_paused(PAUSE_REDEMPTION, clock.unix_timestamp) {
+        return Err(GiftCardError::RedemptionPaused.into());
+    }
+    
     // Verify the gift card account is owned by the program
     if gift_card_account.owner != program_id {
         return Err(ProgramError::IncorrectProgramId);
     }
     
-    // Deserialize the gift card data
-    let mut gift_card = GiftCard::try_from_slice(&gift_card_account.data.borrow())?;
+    // Deserialize the gift card data (optional fields may leave unused trailing space, legacy cards lack them)
+    let mut gift_card = GiftCard::unpack(&gift_card_account.data.borrow())?;
     
     // Verify the gift card belongs to the recipient
     if gift_card.recipient != *recipient.key {
@@ -290,26 +834,46 @@ pub fn process_redeem_gift_card(
     }
     
     // Check if the gift card is expired
-    let clock = Clock::get()?;
     if gift_card.expiry_time > 0 && clock.unix_timestamp as u64 > gift_card.expiry_time {
         return Err(GiftCardError::Expired.into());
     }
     
     // Mark the gift card as redeemed
     gift_card.is_redeemed = true;
-    gift_card.serialize(&mut *gift_card_account.data.borrow_mut())?;
+    gift_card.pack(&mut gift_card_account.data.borrow_mut())?;
     
     // Handle different redemption logic based on token type
     if let Some(mint) = gift_card.token_mint {
         // This is an SPL token gift card - transfer tokens to recipient's token account
         let token_program = next_account_info(accounts_iter)?;
         let recipient_token_account = next_account_info(accounts_iter)?;
+        let mint_entry_account = next_account_info(accounts_iter)?;
         
         // Verify token program ID
         if token_program.key != &token_program_id::ID {
             return Err(GiftCardError::InvalidTokenAccount.into());
         }
         
+        // The destination must hold the card's mint and be the one the recipient asked for
+        let destination_token = TokenAccount::unpack(&recipient_token_account.data.borrow())?;
+        if destination_token.mint != mint {
+            return Err(GiftCardError::InvalidTokenAccount.into());
+        }
+        match destination {
+            Some(destination) if *recipient_token_account.key != destination => {
+                return Err(GiftCardError::InvalidTokenAccount.into());
+            }
+            None if destination_token.owner != *recipient.key => {
+                return Err(GiftCardError::InvalidTokenAccount.into());
+            }
+            _ => {}
+        }
+        
+        // The card no longer counts towards the mint's outstanding liability
+        let mut mint_entry = load_mint_entry(program_id, mint_entry_account, &mint)?;
+        mint_entry.record_redemption(gift_card.amount)?;
+        mint_entry.serialize(&mut *mint_entry_account.data.borrow_mut())?;
+        
         // Get PDA signer
         let (pda, bump_seed) = Pubkey::find_program_address(&[b"gift_card", gift_card_account.key.as_ref()], program_id);
         let seeds = &[b"gift_card".as_ref(), gift_card_account.key.as_ref(), &[bump_seed]];
@@ -332,13 +896,122 @@ pub fn process_redeem_gift_card(
             &[seeds],
         )?;
         
-        msg!("SPL tokens redeemed successfully!");
+        msg!("SPL tokens redeemed successfully to {}!", recipient_token_account.key);
     } else {
-        // This is a SOL gift card - transfer native SOL
-        **recipient.lamports.borrow_mut() = recipient.lamports().checked_add(gift_card_account.lamports()).ok_or(ProgramError::ArithmeticOverflow)?;
+        // This is a SOL gift card - transfer native SOL to the recipient or the chosen destination
+        let payee = match destination {
+            Some(destination) => {
+                let destination_account = next_account_info(accounts_iter)?;
+                if *destination_account.key != destination || destination_account.owner != &system_program::ID {
+                    return Err(GiftCardError::InvalidDestination.into());
+                }
+                destination_account
+            }
+            None => recipient,
+        };
+        **payee.lamports.borrow_mut() = payee.lamports().checked_add(gift_card_account.lamports()).ok_or(ProgramError::ArithmeticOverflow)?;
+        **gift_card_account.lamports.borrow_mut() = 0;
+        
+        msg!("SOL redeemed successfully to {}!", payee.key);
+    }
+    
+    Ok(())
+}
+
+/// Process ReclaimGiftCard instruction
+pub fn process_reclaim_gift_card(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let creator = next_account_info(accounts_iter)?;
+    let gift_card_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    // Verify the creator signed the transaction
+    if !creator.is_signer {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    let config = load_config(program_id, config_account)?;
+    let clock = Clock::get()?;
+    if config.is_paused(PAUSE_REDEMPTION, clock.unix_timestamp) {
+        return Err(GiftCardError::RedemptionPaused.into());
+    }
+    
+    // Verify the gift card account is owned by the program
+    if gift_card_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let mut gift_card = GiftCard::unpack(&gift_card_account.data.borrow())?;
+    
+    if gift_card.creator != *creator.key {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    if gift_card.is_redeemed {
+        return Err(GiftCardError::AlreadyRedeemed.into());
+    }
+    
+    // Only cards that can no longer be redeemed may be reclaimed
+    if gift_card.expiry_time == 0 || clock.unix_timestamp as u64 <= gift_card.expiry_time {
+        return Err(GiftCardError::NotExpired.into());
+    }
+    
+    // Close out the card so it can't be reclaimed twice
+    gift_card.is_redeemed = true;
+    gift_card.pack(&mut gift_card_account.data.borrow_mut())?;
+    
+    if let Some(mint) = gift_card.token_mint {
+        let token_program = next_account_info(accounts_iter)?;
+        let creator_token_account = next_account_info(accounts_iter)?;
+        let mint_entry_account = next_account_info(accounts_iter)?;
+        
+        // Verify token program ID
+        if token_program.key != &token_program_id() {
+            return Err(GiftCardError::InvalidTokenAccount.into());
+        }
+        
+        // The refund goes back to the creator's account for the card's mint
+        let creator_token = TokenAccount::unpack(&creator_token_account.data.borrow())?;
+        if creator_token.mint != mint || creator_token.owner != *creator.key {
+            return Err(GiftCardError::InvalidTokenAccount.into());
+        }
+        
+        // The card no longer counts towards the mint's outstanding liability
+        let mut mint_entry = load_mint_entry(program_id, mint_entry_account, &mint)?;
+        mint_entry.record_redemption(gift_card.amount)?;
+        mint_entry.serialize(&mut *mint_entry_account.data.borrow_mut())?;
+        
+        // Get PDA signer
+        let (pda, bump_seed) = Pubkey::find_program_address(&[b"gift_card", gift_card_account.key.as_ref()], program_id);
+        let seeds = &[b"gift_card".as_ref(), gift_card_account.key.as_ref(), &[bump_seed]];
+        
+        // Transfer tokens from gift card account back to the creator
+        invoke_signed(
+            &token_instruction::transfer(
+                token_program.key,
+                gift_card_account.key,
+                creator_token_account.key,
+                &pda,
+                &[&pda],
+                gift_card.amount,
+            )?,
+            &[
+                gift_card_account.clone(),
+                creator_token_account.clone(),
+                token_program.clone(),
+            ],
+            &[seeds],
+        )?;
+        
+        msg!("Expired SPL token gift card reclaimed by {}", creator.key);
+    } else {
+        **creator.lamports.borrow_mut() = creator.lamports().checked_add(gift_card_account.lamports()).ok_or(ProgramError::ArithmeticOverflow)?;
         **gift_card_account.lamports.borrow_mut() = 0;
         
-        msg!("SOL redeemed successfully!");
+        msg!("Expired SOL gift card reclaimed by {}", creator.key);
     }
     
     Ok(())
@@ -355,12 +1028,22 @@ pub fn process_stake(
     let staker = next_account_info(accounts_iter)?;
     let staking_pool_account = next_account_info(accounts_iter)?;
     let system_program = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
     
     // Verify the staker signed the transaction
     if !staker.is_signer {
         return Err(GiftCardError::NotAuthorized.into());
     }
     
+    if amount == 0 {
+        return Err(GiftCardError::InvalidStakeAmount.into());
+    }
+    
+    let config = load_config(program_id, config_account)?;
+    if config.is_paused(PAUSE_STAKING, Clock::get()?.unix_timestamp) {
+        return Err(GiftCardError::StakingPaused.into());
+    }
+    
     // Verify the staking pool account is owned by the program
     if staking_pool_account.owner != program_id {
         // If the account doesn't exist yet, create it
@@ -380,9 +1063,9 @@ pub fn process_stake(
             &[staker.clone(), staking_pool_account.clone(), system_program.clone()],
         )?;
         
-        // Initialize staking pool data
+        // Initialize staking pool data; the pool is administered by the config admin
         let staking_pool = StakingPool {
-            admin: *staker.key,
+            admin: config.admin,
             total_staked: 0,
             stakers: Vec::new(),
         };
@@ -429,6 +1112,7 @@ pub fn process_distribute_rewards(
     
     let admin = next_account_info(accounts_iter)?;
     let staking_pool_account = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
     
     // Verify the admin signed the transaction
     if !admin.is_signer {
@@ -443,19 +1127,23 @@ pub fn process_distribute_rewards(
     // Deserialize the staking pool data
     let staking_pool = StakingPool::try_from_slice(&staking_pool_account.data.borrow())?;
     
-    // Verify the signer is the admin
-    if staking_pool.admin != *admin.key {
+    // Verify the signer is the current config admin
+    let config = load_config(program_id, config_account)?;
+    if config.admin != *admin.key {
         return Err(GiftCardError::NotAuthorized.into());
     }
     
     // Get the current timestamp for randomness
     let clock = Clock::get()?;
+    if config.is_paused(PAUSE_STAKING, clock.unix_timestamp) {
+        return Err(GiftCardError::StakingPaused.into());
+    }
     let timestamp = clock.unix_timestamp as u64;
     
     // Simple random number generation using timestamp (for demonstration only)
     // In production, this should use a proper random source like Chainlink VRF
     let mut recipients = Vec::new();
-    let num_recipients = std::cmp::min(accounts.len() - 2, staking_pool.stakers.len());
+    let num_recipients = std::cmp::min(accounts.len() - 3, staking_pool.stakers.len());
     
     // Collect the recipient accounts
     for _ in 0..num_recipients {
@@ -482,4 +1170,534 @@ pub fn process_distribute_rewards(
     msg!("Rewards distributed successfully!");
     
     Ok(())
-} 
\ No newline at end of file
+} 
+
+/// Load the program config, verifying it is the canonical config PDA
+pub fn load_config(
+    program_id: &Pubkey,
+    config_account: &AccountInfo,
+) -> Result<ProgramConfig, ProgramError> {
+    let (config_pda, _) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+    if *config_account.key != config_pda || config_account.owner != program_id {
+        return Err(GiftCardError::InvalidConfig.into());
+    }
+    
+    let config = ProgramConfig::deserialize(&mut &config_account.data.borrow()[..])?;
+    Ok(config)
+}
+
+/// Load a mint registry entry, verifying it is the canonical PDA for `mint`
+pub fn load_mint_entry(
+    program_id: &Pubkey,
+    mint_entry_account: &AccountInfo,
+    mint: &Pubkey,
+) -> Result<MintEntry, ProgramError> {
+    let (entry_pda, _) = Pubkey::find_program_address(&[MINT_ENTRY_SEED, mint.as_ref()], program_id);
+    if *mint_entry_account.key != entry_pda || mint_entry_account.owner != program_id {
+        return Err(GiftCardError::MintNotAllowed.into());
+    }
+    
+    let mint_entry = MintEntry::deserialize(&mut &mint_entry_account.data.borrow()[..])?;
+    Ok(mint_entry)
+}
+
+/// Upgrade authority recorded in this program's ProgramData account
+fn upgrade_authority(program_id: &Pubkey, program_data_account: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+    let (program_data_address, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if *program_data_account.key != program_data_address || program_data_account.owner != &bpf_loader_upgradeable::id() {
+        return Err(GiftCardError::InvalidConfig.into());
+    }
+    
+    // ProgramData metadata is bincode: u32 variant tag, u64 slot, Option<Pubkey> authority
+    let data = program_data_account.data.borrow();
+    let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+    if data.len() < metadata_len || data[..4] != 3u32.to_le_bytes() {
+        return Err(GiftCardError::InvalidConfig.into());
+    }
+    match data[12] {
+        0 => Ok(None),
+        1 => Ok(Some(Pubkey::new_from_array(data[13..metadata_len].try_into().unwrap()))),
+        _ => Err(GiftCardError::InvalidConfig.into()),
+    }
+}
+
+/// Create a program-owned PDA, even if someone has already sent lamports to its address
+fn create_pda_account<'a>(
+    payer: &AccountInfo<'a>,
+    account: &AccountInfo<'a>,
+    system_program: &AccountInfo<'a>,
+    space: usize,
+    program_id: &Pubkey,
+    seeds: &[&[u8]],
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space);
+    
+    if account.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(payer.key, account.key, lamports, space as u64, program_id),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[seeds],
+        );
+    }
+    
+    // create_account fails on a funded address, so top up the rent, then allocate and assign
+    let top_up = lamports.saturating_sub(account.lamports());
+    if top_up > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, top_up),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program.clone()],
+        &[seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, program_id),
+        &[account.clone(), system_program.clone()],
+        &[seeds],
+    )
+}
+
+/// Process InitializeConfig instruction
+pub fn process_initialize_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_card_amount: u64,
+    max_card_amount: u64,
+    max_message_len: u32,
+    protocol_fee_bps: u16,
+    fee_recipient: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let program_data_account = next_account_info(accounts_iter)?;
+    
+    // Verify the admin signed the transaction
+    if !admin.is_signer {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    // Only the upgrade authority may claim the config, so it can't be front-run after deploy
+    if upgrade_authority(program_id, program_data_account)? != Some(*admin.key) {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    // The config can only be created once, at the canonical PDA
+    let (config_pda, bump_seed) = Pubkey::find_program_address(&[CONFIG_SEED], program_id);
+    if *config_account.key != config_pda || config_account.owner == program_id {
+        return Err(GiftCardError::InvalidConfig.into());
+    }
+    
+    let config = ProgramConfig {
+        admin: *admin.key,
+        pending_admin: None,
+        min_card_amount,
+        max_card_amount,
+        max_message_len,
+        protocol_fee_bps,
+        fee_recipient,
+        pauser: *admin.key,
+        pause_flags: 0,
+        redemption_paused_at: 0,
+        bump: bump_seed,
+    };
+    config.validate()?;
+    
+    // Create the config account (PDA)
+    create_pda_account(
+        admin,
+        config_account,
+        system_program,
+        ProgramConfig::LEN,
+        program_id,
+        &[CONFIG_SEED, &[bump_seed]],
+    )?;
+    
+    config.serialize(&mut *config_account.data.borrow_mut())?;
+    
+    msg!("Program config initialized!");
+    
+    Ok(())
+}
+
+/// Process UpdateConfig instruction
+pub fn process_update_config(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_card_amount: u64,
+    max_card_amount: u64,
+    max_message_len: u32,
+    protocol_fee_bps: u16,
+    fee_recipient: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    let mut config = load_config(program_id, config_account)?;
+    
+    // Verify the admin signed the transaction
+    if !admin.is_signer || config.admin != *admin.key {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    config.min_card_amount = min_card_amount;
+    config.max_card_amount = max_card_amount;
+    config.max_message_len = max_message_len;
+    config.protocol_fee_bps = protocol_fee_bps;
+    config.fee_recipient = fee_recipient;
+    config.validate()?;
+    
+    config.serialize(&mut *config_account.data.borrow_mut())?;
+    
+    msg!("Program config updated!");
+    
+    Ok(())
+}
+
+/// Process TransferAdmin instruction
+pub fn process_transfer_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_admin: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    let mut config = load_config(program_id, config_account)?;
+    
+    // Verify the admin signed the transaction
+    if !admin.is_signer || config.admin != *admin.key {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    config.pending_admin = Some(new_admin);
+    config.serialize(&mut *config_account.data.borrow_mut())?;
+    
+    msg!("Admin transfer proposed to {}", new_admin);
+    
+    Ok(())
+}
+
+/// Process AcceptAdmin instruction
+pub fn process_accept_admin(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let pending_admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    let mut config = load_config(program_id, config_account)?;
+    
+    // Only the nominated account can accept the transfer
+    if !pending_admin.is_signer || config.pending_admin != Some(*pending_admin.key) {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    config.admin = *pending_admin.key;
+    config.pending_admin = None;
+    config.serialize(&mut *config_account.data.borrow_mut())?;
+    
+    msg!("Admin transferred to {}", pending_admin.key);
+    
+    Ok(())
+}
+
+/// Process SetPauseFlags instruction
+pub fn process_set_pause_flags(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    flags: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let pauser = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    let mut config = load_config(program_id, config_account)?;
+    
+    // Either the pauser or the admin may flip the switch
+    if !pauser.is_signer || (config.pauser != *pauser.key && config.admin != *pauser.key) {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    // The redemption pause window is bounded and can't be restarted back-to-back
+    config.set_pause_flags(flags, Clock::get()?.unix_timestamp)?;
+    config.serialize(&mut *config_account.data.borrow_mut())?;
+    
+    msg!("Pause flags set to {:#06b}", flags);
+    
+    Ok(())
+}
+
+/// Process SetPauser instruction
+pub fn process_set_pauser(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pauser: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    
+    let mut config = load_config(program_id, config_account)?;
+    
+    // Verify the admin signed the transaction
+    if !admin.is_signer || config.admin != *admin.key {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    config.pauser = pauser;
+    config.serialize(&mut *config_account.data.borrow_mut())?;
+    
+    msg!("Pauser set to {}", pauser);
+    
+    Ok(())
+}
+
+/// Process RegisterMint instruction
+pub fn process_register_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    min_card_amount: u64,
+    max_card_amount: u64,
+    per_card_cap: u64,
+    liability_cap: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let mint_entry_account = next_account_info(accounts_iter)?;
+    let mint_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    let config = load_config(program_id, config_account)?;
+    
+    // Verify the admin signed the transaction
+    if !admin.is_signer || config.admin != *admin.key {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    // The mint must be a real SPL mint
+    if mint_account.owner != &token_program_id() {
+        return Err(GiftCardError::InvalidTokenAccount.into());
+    }
+    
+    // Each mint gets exactly one entry, at the canonical PDA
+    let (entry_pda, bump_seed) = Pubkey::find_program_address(&[MINT_ENTRY_SEED, mint_account.key.as_ref()], program_id);
+    if *mint_entry_account.key != entry_pda || mint_entry_account.owner == program_id {
+        return Err(GiftCardError::InvalidMintEntry.into());
+    }
+    
+    let mint_entry = MintEntry {
+        mint: *mint_account.key,
+        enabled: true,
+        min_card_amount,
+        max_card_amount,
+        per_card_cap,
+        liability_cap,
+        outstanding: 0,
+        bump: bump_seed,
+    };
+    mint_entry.validate()?;
+    
+    // Create the mint registry entry (PDA)
+    create_pda_account(
+        admin,
+        mint_entry_account,
+        system_program,
+        MintEntry::LEN,
+        program_id,
+        &[MINT_ENTRY_SEED, mint_account.key.as_ref(), &[bump_seed]],
+    )?;
+    
+    mint_entry.serialize(&mut *mint_entry_account.data.borrow_mut())?;
+    
+    msg!("Mint {} registered", mint_account.key);
+    
+    Ok(())
+}
+
+/// Process UpdateMint instruction
+pub fn process_update_mint(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    enabled: bool,
+    min_card_amount: u64,
+    max_card_amount: u64,
+    per_card_cap: u64,
+    liability_cap: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let admin = next_account_info(accounts_iter)?;
+    let config_account = next_account_info(accounts_iter)?;
+    let mint_entry_account = next_account_info(accounts_iter)?;
+    
+    let config = load_config(program_id, config_account)?;
+    
+    // Verify the admin signed the transaction
+    if !admin.is_signer || config.admin != *admin.key {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    
+    if mint_entry_account.owner != program_id {
+        return Err(GiftCardError::InvalidMintEntry.into());
+    }
+    let mut mint_entry = MintEntry::deserialize(&mut &mint_entry_account.data.borrow()[..])?;
+    let entry_pda = Pubkey::create_program_address(&[MINT_ENTRY_SEED, mint_entry.mint.as_ref(), &[mint_entry.bump]], program_id)?;
+    if *mint_entry_account.key != entry_pda {
+        return Err(GiftCardError::InvalidMintEntry.into());
+    }
+    
+    // Lowering the liability cap below the outstanding total only blocks new cards
+    mint_entry.enabled = enabled;
+    mint_entry.min_card_amount = min_card_amount;
+    mint_entry.max_card_amount = max_card_amount;
+    mint_entry.per_card_cap = per_card_cap;
+    mint_entry.liability_cap = liability_cap;
+    mint_entry.validate()?;
+    
+    mint_entry.serialize(&mut *mint_entry_account.data.borrow_mut())?;
+    
+    msg!("Mint {} updated", mint_entry.mint);
+    
+    Ok(())
+}
+
+/// Process UpdateCardMetadata instruction
+pub fn process_update_card_metadata(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    metadata_uri: Option<String>,
+    content_hash: Option<[u8; 32]>,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    
+    let creator = next_account_info(accounts_iter)?;
+    let gift_card_account = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    
+    // Verify the gift card account is owned by the program
+    if gift_card_account.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+    
+    let mut gift_card = GiftCard::unpack(&gift_card_account.data.borrow())?;
+    
+    // Only the creator may change the metadata, and only before redemption
+    if !creator.is_signer || gift_card.creator != *creator.key {
+        return Err(GiftCardError::NotAuthorized.into());
+    }
+    if gift_card.is_redeemed {
+        return Err(GiftCardError::AlreadyRedeemed.into());
+    }
+    if metadata_uri.as_ref().is_some_and(|uri| uri.len() > MAX_METADATA_URI_LEN) {
+        return Err(GiftCardError::UriTooLong.into());
+    }
+    
+    // Resize the account to fit the new URI, topping up rent from the creator
+    let space = GiftCard::space(&gift_card.message, &metadata_uri);
+    if space != gift_card_account.data_len() {
+        let rent = Rent::get()?;
+        let required_lamports = rent.minimum_balance(space)
+            .checked_add(gift_card.held_lamports())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let shortfall = required_lamports.saturating_sub(gift_card_account.lamports());
+        if shortfall > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    creator.key,
+                    gift_card_account.key,
+                    shortfall,
+                ),
+                &[creator.clone(), gift_card_account.clone(), system_program.clone()],
+            )?;
+        }
+        gift_card_account.realloc(space, false)?;
+    }
+    
+    gift_card.metadata_uri = metadata_uri;
+    gift_card.content_hash = content_hash;
+    gift_card.pack(&mut gift_card_account.data.borrow_mut())?;
+    
+    msg!("Gift card metadata updated!");
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn legacy_card(token_mint: Option<Pubkey>) -> LegacyGiftCard {
+        LegacyGiftCard {
+            creator: Pubkey::new_unique(),
+            recipient: Pubkey::new_unique(),
+            amount: 1_000,
+            is_redeemed: false,
+            expiry_time: 0,
+            message: "Happy birthday".to_string(),
+            token_mint,
+            theme_id: 2,
+        }
+    }
+    
+    #[test]
+    fn unpacks_legacy_token_card() {
+        // SPL cards used to be created at exactly the legacy size
+        let legacy = legacy_card(Some(Pubkey::new_unique()));
+        let data = legacy.try_to_vec().unwrap();
+        assert!(GiftCard::deserialize(&mut &data[..]).is_err());
+        
+        let gift_card = GiftCard::unpack(&data).unwrap();
+        assert_eq!(gift_card, GiftCard::from(legacy));
+        assert_eq!(gift_card.metadata_uri, None);
+        assert_eq!(gift_card.content_hash, None);
+    }
+    
+    #[test]
+    fn packs_redeemed_legacy_card_in_place() {
+        let mut data = legacy_card(Some(Pubkey::new_unique())).try_to_vec().unwrap();
+        let len = data.len();
+        
+        let mut gift_card = GiftCard::unpack(&data).unwrap();
+        gift_card.is_redeemed = true;
+        gift_card.pack(&mut data).unwrap();
+        
+        assert_eq!(data.len(), len);
+        assert!(GiftCard::unpack(&data).unwrap().is_redeemed);
+    }
+    
+    #[test]
+    fn legacy_card_needs_realloc_for_metadata() {
+        let mut data = legacy_card(Some(Pubkey::new_unique())).try_to_vec().unwrap();
+        
+        let mut gift_card = GiftCard::unpack(&data).unwrap();
+        gift_card.content_hash = Some([1u8; 32]);
+        assert_eq!(gift_card.pack(&mut data), Err(ProgramError::AccountDataTooSmall));
+    }
+    
+    #[test]
+    fn round_trips_current_layout_with_spare_space() {
+        let mut gift_card = GiftCard::from(legacy_card(None));
+        gift_card.metadata_uri = Some("https://example.com/card.json".to_string());
+        gift_card.content_hash = Some([7u8; 32]);
+        
+        let mut data = vec![0u8; GiftCard::space(&gift_card.message, &gift_card.metadata_uri) + 16];
+        gift_card.pack(&mut data).unwrap();
+        assert_eq!(GiftCard::unpack(&data).unwrap(), gift_card);
+    }
+}
\ No newline at end of file
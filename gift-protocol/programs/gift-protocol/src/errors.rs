@@ -55,4 +55,148 @@ pub enum GiftProtocolError {
 
     #[msg("Maximum number of votes exceeded")]
     MaxVotesExceeded,
+
+    #[msg("Gift card creation is paused")]
+    CreationPaused,
+
+    #[msg("Gift card redemption is paused")]
+    RedemptionPaused,
+
+    #[msg("Treasury staking is paused")]
+    StakingPaused,
+
+    #[msg("Governance is paused")]
+    GovernancePaused,
+
+    #[msg("Gift card is already tokenized")]
+    AlreadyTokenized,
+
+    #[msg("Gift card NFT must be burned to redeem")]
+    NftRequired,
+
+    #[msg("Invalid gift card NFT")]
+    InvalidNft,
+
+    #[msg("URI too long")]
+    UriTooLong,
+
+    #[msg("Invalid Merkle proof")]
+    InvalidProof,
+
+    #[msg("Campaign leaf already claimed")]
+    AlreadyClaimed,
+
+    #[msg("Invalid campaign leaf index")]
+    InvalidLeafIndex,
+
+    #[msg("Campaign claim period has ended")]
+    CampaignEnded,
+
+    #[msg("Campaign claim period is still open")]
+    CampaignActive,
+
+    #[msg("Unsupported message encryption scheme")]
+    UnsupportedMessageScheme,
+
+    #[msg("Encrypted message chunk does not match the stored header")]
+    EncryptedMessageMismatch,
+
+    #[msg("Theme does not exist")]
+    InvalidTheme,
+
+    #[msg("Theme is not active")]
+    ThemeInactive,
+
+    #[msg("Theme registry is full")]
+    ThemeRegistryFull,
+
+    #[msg("Name too long")]
+    NameTooLong,
+
+    #[msg("Missing or invalid recipient signature for relayed redemption")]
+    InvalidRelaySignature,
+
+    #[msg("Relayed redemption request expired")]
+    RelayRequestExpired,
+
+    #[msg("Relayer fee exceeds the signed cap")]
+    RelayerFeeTooHigh,
+
+    #[msg("Invalid redemption destination")]
+    InvalidDestination,
+
+    #[msg("Too many merchants for a gift card")]
+    TooManyMerchants,
+
+    #[msg("Gift card can only be spent at its merchants")]
+    ClosedLoopCard,
+
+    #[msg("Merchant is not accepted by this gift card")]
+    MerchantNotAccepted,
+
+    #[msg("Merchant is not active")]
+    MerchantInactive,
+
+    #[msg("Insufficient gift card balance")]
+    InsufficientBalance,
+
+    #[msg("Gift card listing is stale")]
+    StaleListing,
+
+    #[msg("Payment accounts do not match the listing")]
+    InvalidPaymentAccount,
+
+    #[msg("Gift card has not passed its expiry grace period")]
+    NotExpired,
+
+    #[msg("Invalid breakage policy")]
+    InvalidBreakagePolicy,
+
+    #[msg("Gift card amount exceeds the per-card limit")]
+    CardAmountLimitExceeded,
+
+    #[msg("Creator issuance velocity limit exceeded")]
+    VelocityLimitExceeded,
+
+    #[msg("Cannot refer yourself")]
+    SelfReferral,
+
+    #[msg("Referral chain contains a cycle")]
+    ReferralCycle,
+
+    #[msg("Referral codes must be 3-16 letters or digits")]
+    InvalidReferralCode,
+
+    #[msg("Invalid promo campaign")]
+    InvalidPromoCampaign,
+
+    #[msg("Insufficient treasury funds")]
+    InsufficientFunds,
+
+    #[msg("Proposal has no winning choice with actions to execute")]
+    ProposalNotApproved,
+
+    #[msg("Proposal timelock has not elapsed")]
+    TimelockActive,
+
+    #[msg("Proposal already executed")]
+    ProposalAlreadyExecuted,
+
+    #[msg("Too many actions for a proposal choice")]
+    TooManyActions,
+
+    #[msg("Proposal is not open for voting")]
+    ProposalNotActive,
+
+    #[msg("Proposal can no longer be changed")]
+    InvalidProposalState,
+
+    #[msg("Redemption can't be paused again until the cooldown after the last pause ends")]
+    RedemptionPauseCooldown,
+
+    #[msg("Gift card is listed for sale")]
+    CardListed,
+
+    #[msg("Account is already in the current layout")]
+    AlreadyMigrated,
 }
\ No newline at end of file
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+// Emitted when a gift card's funds are paid out
+#[event]
+pub struct GiftCardRedeemed {
+    pub gift_card: Pubkey,
+    pub redeemer: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted when a merchant is listed in the registry
+#[event]
+pub struct MerchantRegistered {
+    pub merchant: Pubkey,
+    pub owner: Pubkey,
+    pub settlement: Pubkey,
+    pub approved_by_proposal: Option<Pubkey>,
+}
+
+// Emitted when a card pays a merchant
+#[event]
+pub struct MerchantPaid {
+    pub gift_card: Pubkey,
+    pub merchant: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub remaining: u64,
+}
+
+// Emitted when update_config changes the protocol rates
+#[event]
+pub struct ConfigUpdated {
+    pub old_commission_rate: u64,
+    pub new_commission_rate: u64,
+    pub old_referral_rate: u64,
+    pub new_referral_rate: u64,
+    pub old_settlement_fee_bps: u64,
+    pub new_settlement_fee_bps: u64,
+    pub old_marketplace_fee_bps: u64,
+    pub new_marketplace_fee_bps: u64,
+}
+
+// Emitted when the authority proposes a successor
+#[event]
+pub struct AuthorityProposed {
+    pub authority: Pubkey,
+    pub pending_authority: Pubkey,
+}
+
+// Emitted when the proposed authority accepts the handover
+#[event]
+pub struct AuthorityTransferred {
+    pub old_authority: Pubkey,
+    pub new_authority: Pubkey,
+}
+
+// Emitted when an approved proposal pays out of the treasury
+#[event]
+pub struct TreasurySpent {
+    pub proposal: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub mint: Option<Pubkey>,
+}
+
+// Emitted when a card is listed for sale
+#[event]
+pub struct GiftCardListed {
+    pub gift_card: Pubkey,
+    pub seller: Pubkey,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+}
+
+// Emitted when a listed card is bought
+#[event]
+pub struct GiftCardSold {
+    pub gift_card: Pubkey,
+    pub seller: Pubkey,
+    pub buyer: Pubkey,
+    pub price: u64,
+    pub payment_mint: Option<Pubkey>,
+    pub fee: u64,
+}
+
+// Emitted when a referrer withdraws their earnings
+#[event]
+pub struct ReferralEarningsClaimed {
+    pub referral: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+}
+
+// Emitted when an expired card is cranked and closed
+#[event]
+pub struct GiftCardReclaimed {
+    pub gift_card: Pubkey,
+    pub creator: Pubkey,
+    pub cranker: Pubkey,
+    pub refunded: u64,
+    pub breakage: u64,
+    pub bounty: u64,
+}
\ No newline at end of file
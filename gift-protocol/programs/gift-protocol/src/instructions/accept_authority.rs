@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct AcceptAuthority<'info> {
+    #[account(
+        constraint = config.pending_authority == Some(new_authority.key()) @ GiftProtocolError::NotAuthorized
+    )]
+    pub new_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<AcceptAuthority>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    let old_authority = config.authority;
+    config.authority = ctx.accounts.new_authority.key();
+    config.pending_authority = None;
+
+    emit!(AuthorityTransferred {
+        old_authority,
+        new_authority: config.authority,
+    });
+
+    msg!("Authority transferred from {} to {}", old_authority, config.authority);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ActivateProposal<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_GOVERNANCE, clock.unix_timestamp) @ GiftProtocolError::GovernancePaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.creator == creator.key() @ GiftProtocolError::NotAuthorized,
+        constraint = proposal.state == ProposalState::Draft @ GiftProtocolError::InvalidProposalState,
+        constraint = proposal.voting_end_time > clock.unix_timestamp @ GiftProtocolError::ProposalVotingEnded,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ActivateProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    proposal.state = ProposalState::Active;
+
+    msg!("Proposal {} open for voting until {}", proposal.proposal_id, proposal.voting_end_time);
+
+    Ok(())
+}
\ No newline at end of file
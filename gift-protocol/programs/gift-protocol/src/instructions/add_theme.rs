@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct AddTheme<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"theme_registry".as_ref()],
+        bump = theme_registry.bump,
+    )]
+    pub theme_registry: Account<'info, ThemeRegistry>,
+}
+
+pub fn handler(
+    ctx: Context<AddTheme>,
+    name: String,
+    artwork_uri: String,
+    premium_bps: u16,
+) -> Result<()> {
+    // Validate inputs
+    require!(!name.is_empty() && name.len() <= MAX_THEME_NAME_LEN, GiftProtocolError::NameTooLong);
+    require!(artwork_uri.len() <= MAX_METADATA_URI_LEN, GiftProtocolError::UriTooLong);
+    require!(premium_bps <= 10000, GiftProtocolError::InvalidCommissionRate);
+
+    let theme_registry = &mut ctx.accounts.theme_registry;
+    require!(theme_registry.themes.len() < MAX_THEMES, GiftProtocolError::ThemeRegistryFull);
+
+    // Theme ids are assigned in registration order
+    let theme_id = theme_registry.themes.len();
+    theme_registry.themes.push(Theme {
+        name,
+        artwork_uri,
+        is_active: true,
+        premium_bps,
+    });
+
+    msg!("Theme {} added with {}bp premium", theme_id, premium_bps);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(scheme: u8, ephemeral_pubkey: [u8; 32], chunk: Vec<u8>)]
+pub struct AppendEncryptedMessage<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    // Grow the card by the chunk, plus the header on the first write
+    #[account(
+        mut,
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
+        bump = gift_card.bump,
+        has_one = creator @ GiftProtocolError::NotAuthorized,
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed,
+        realloc = gift_card.to_account_info().data_len() + chunk.len()
+            + if gift_card.encrypted_message.is_none() { EncryptedMessage::HEADER_LEN } else { 0 },
+        realloc::payer = creator,
+        realloc::zero = false,
+    )]
+    pub gift_card: Account<'info, GiftCard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<AppendEncryptedMessage>,
+    scheme: u8,
+    ephemeral_pubkey: [u8; 32],
+    chunk: Vec<u8>,
+) -> Result<()> {
+    // Validate inputs
+    require!(scheme == MESSAGE_SCHEME_X25519_CHACHA20POLY1305, GiftProtocolError::UnsupportedMessageScheme);
+    require!(!chunk.is_empty(), GiftProtocolError::InvalidInstruction);
+
+    let gift_card = &mut ctx.accounts.gift_card;
+    match gift_card.encrypted_message.as_mut() {
+        Some(encrypted_message) => {
+            // Later chunks must continue the same message
+            require!(
+                encrypted_message.scheme == scheme && encrypted_message.ephemeral_pubkey == ephemeral_pubkey,
+                GiftProtocolError::EncryptedMessageMismatch
+            );
+            require!(
+                encrypted_message.ciphertext.len() + chunk.len() <= MAX_CIPHERTEXT_LEN,
+                GiftProtocolError::MessageTooLong
+            );
+            encrypted_message.ciphertext.extend_from_slice(&chunk);
+        }
+        None => {
+            require!(chunk.len() <= MAX_CIPHERTEXT_LEN, GiftProtocolError::MessageTooLong);
+            gift_card.encrypted_message = Some(EncryptedMessage {
+                scheme,
+                ephemeral_pubkey,
+                ciphertext: chunk,
+            });
+        }
+    }
+
+    msg!("Encrypted message chunk appended to gift card");
+
+    Ok(())
+}
\ No newline at end of file
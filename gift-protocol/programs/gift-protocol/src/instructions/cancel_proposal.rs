@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CancelProposal<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.creator == creator.key() @ GiftProtocolError::NotAuthorized,
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+pub fn handler(ctx: Context<CancelProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+
+    // Drafts can always be withdrawn; active proposals only before anyone votes
+    let cancellable = match proposal.state {
+        ProposalState::Draft => true,
+        ProposalState::Active => proposal.total_votes == 0,
+        _ => false,
+    };
+    require!(cancellable, GiftProtocolError::InvalidProposalState);
+
+    proposal.state = ProposalState::Cancelled;
+
+    msg!("Proposal {} cancelled", proposal.proposal_id);
+
+    Ok(())
+}
\ No newline at end of file
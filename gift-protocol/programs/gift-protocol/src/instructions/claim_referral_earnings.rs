@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ClaimReferralEarnings<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral".as_ref(), owner.key().as_ref()],
+        bump = referral.bump,
+        constraint = referral.owner == owner.key() @ GiftProtocolError::NotAuthorized,
+        constraint = referral.unclaimed_earnings > 0 @ GiftProtocolError::InvalidAmount
+    )]
+    pub referral: Account<'info, Referral>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+    let referral = &mut ctx.accounts.referral;
+    let amount = referral.unclaimed_earnings;
+
+    referral.unclaimed_earnings = 0;
+    referral.total_claimed = referral.total_claimed.checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // Pay out of the referral account, leaving its rent in place
+    let referral_info = referral.to_account_info();
+    let owner_info = ctx.accounts.owner.to_account_info();
+    **referral_info.lamports.borrow_mut() = referral_info.lamports()
+        .checked_sub(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **owner_info.lamports.borrow_mut() = owner_info.lamports()
+        .checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    // total_referral_payouts - total_referral_claimed equals the sum of unclaimed vault balances
+    let config = &mut ctx.accounts.config;
+    config.total_referral_claimed = config.total_referral_claimed.checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    emit!(ReferralEarningsClaimed {
+        referral: referral.key(),
+        owner: owner_info.key(),
+        amount,
+    });
+
+    msg!("Referral earnings claimed: {} lamports", amount);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct CloseCampaign<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    // Closing returns the unclaimed funds and the rent to the creator
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"campaign".as_ref(), creator.key().as_ref(), &campaign.campaign_id.to_le_bytes()],
+        bump = campaign.bump,
+        has_one = creator @ GiftProtocolError::NotAuthorized,
+        constraint = campaign.claim_deadline <= clock.unix_timestamp @ GiftProtocolError::CampaignActive,
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<CloseCampaign>) -> Result<()> {
+    let campaign = &ctx.accounts.campaign;
+    let unclaimed = campaign.total_amount.checked_sub(campaign.claimed_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    msg!("Campaign {} closed: {} of {} leaves claimed, {} lamports returned",
+        campaign.campaign_id, campaign.num_claimed, campaign.num_leaves, unclaimed);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct CrankExpired<'info> {
+    /// Anyone may crank; they receive the bounty
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    /// CHECK: The card's creator, who receives the refunded funds and remaining rent
+    #[account(
+        mut,
+        address = gift_card.creator @ GiftProtocolError::NotAuthorized
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
+        bump = gift_card.bump,
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed
+    )]
+    pub gift_card: Account<'info, GiftCard>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump = treasury.bump,
+        constraint = treasury.key() == config.treasury @ GiftProtocolError::TreasuryMismatch
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<CrankExpired>) -> Result<()> {
+    let gift_card = &ctx.accounts.gift_card;
+    let config = &mut ctx.accounts.config;
+
+    // Only cards with an expiry, and only once the grace period has passed
+    let crankable_at = gift_card.expiry_time.saturating_add(config.expiry_grace_period);
+    require!(
+        gift_card.expiry_time > 0 && ctx.accounts.clock.unix_timestamp > crankable_at,
+        GiftProtocolError::NotExpired
+    );
+
+    // The bounty comes out of the rent, never out of the card's balance
+    let gift_card_info = gift_card.to_account_info();
+    let total_lamports = gift_card_info.lamports();
+    let rent_lamports = total_lamports.saturating_sub(gift_card.amount);
+    let bounty = config.crank_bounty.min(rent_lamports);
+
+    // The breakage policy the card was issued under decides how much of the balance the treasury keeps
+    let breakage = gift_card.breakage_for(gift_card.amount.min(total_lamports));
+
+    if bounty > 0 {
+        let cranker_info = ctx.accounts.cranker.to_account_info();
+        **cranker_info.lamports.borrow_mut() = cranker_info.lamports()
+            .checked_add(bounty)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    if breakage > 0 {
+        let treasury_info = ctx.accounts.treasury.to_account_info();
+        **treasury_info.lamports.borrow_mut() = treasury_info.lamports()
+            .checked_add(breakage)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        let treasury = &mut ctx.accounts.treasury;
+        treasury.balance = treasury.balance.checked_add(breakage)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        config.total_breakage = config.total_breakage.checked_add(breakage)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+    }
+
+    // Whatever is left goes to the creator when the account closes
+    let refunded = total_lamports - bounty - breakage;
+    **gift_card_info.lamports.borrow_mut() = refunded;
+
+    emit!(GiftCardReclaimed {
+        gift_card: gift_card.key(),
+        creator: gift_card.creator,
+        cranker: ctx.accounts.cranker.key(),
+        refunded,
+        breakage,
+        bounty,
+    });
+
+    msg!("Expired gift card {} reclaimed: {} lamports to creator, {} breakage, {} bounty", gift_card.key(), refunded, breakage, bounty);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(campaign_id: u64, merkle_root: [u8; 32], num_leaves: u32)]
+pub struct CreateCampaign<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<Campaign>() + (num_leaves as usize).div_ceil(8),
+        seeds = [b"campaign".as_ref(), creator.key().as_ref(), &campaign_id.to_le_bytes()],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_CREATION, clock.unix_timestamp) @ GiftProtocolError::CreationPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<CreateCampaign>,
+    campaign_id: u64,
+    merkle_root: [u8; 32],
+    num_leaves: u32,
+    total_amount: u64,
+    claim_deadline: i64,
+) -> Result<()> {
+    // Validate inputs
+    require!(total_amount > 0, GiftProtocolError::InvalidAmount);
+    require!(num_leaves > 0 && num_leaves <= MAX_CAMPAIGN_LEAVES, GiftProtocolError::InvalidLeafIndex);
+    require!(claim_deadline > ctx.accounts.clock.unix_timestamp, GiftProtocolError::Expired);
+
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.creator = ctx.accounts.creator.key();
+    campaign.campaign_id = campaign_id;
+    campaign.merkle_root = merkle_root;
+    campaign.num_leaves = num_leaves;
+    campaign.total_amount = total_amount;
+    campaign.claimed_amount = 0;
+    campaign.num_claimed = 0;
+    campaign.claim_deadline = claim_deadline;
+    campaign.claimed_bitmap = vec![0; (num_leaves as usize).div_ceil(8)];
+    campaign.bump = *ctx.bumps.get("campaign").unwrap();
+
+    // Fund the campaign vault
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.creator.to_account_info(),
+        to: campaign.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.system_program.to_account_info();
+    let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+    system_program::transfer(cpi_ctx, total_amount)?;
+
+    msg!("Campaign {} created: {} lamports across {} leaves", campaign_id, total_amount, num_leaves);
+
+    Ok(())
+}
\ No newline at end of file
@@ -3,8 +3,26 @@ use anchor_lang::system_program;
 use crate::state::*;
 use crate::errors::*;
 
+// Terms of a new gift card
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct GiftCardParams {
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub expiry_time: i64,
+    pub message: String,
+    pub referrer: Option<Pubkey>,
+    pub theme_id: u8,
+
+    // Off-chain metadata and the hash of its content
+    pub metadata_uri: Option<String>,
+    pub content_hash: Option<[u8; 32]>,
+
+    // Merchants a closed-loop card can be spent at (empty for an open card)
+    pub merchants: Vec<Pubkey>,
+}
+
 #[derive(Accounts)]
-#[instruction(amount: u64, recipient: Pubkey, expiry_time: i64, message: String, referrer: Option<Pubkey>)]
+#[instruction(card_id: u64, params: GiftCardParams)]
 pub struct CreateGiftCard<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -12,8 +30,8 @@ pub struct CreateGiftCard<'info> {
     #[account(
         init,
         payer = creator,
-        space = 8 + std::mem::size_of::<GiftCard>() + message.len(),
-        seeds = [b"gift_card".as_ref(), creator.key().as_ref(), &Pubkey::default().to_bytes()],
+        space = 8 + std::mem::size_of::<GiftCard>() + params.message.len() + params.metadata_uri.as_ref().map_or(0, |uri| uri.len()) + params.merchants.len() * 32,
+        seeds = [b"gift_card".as_ref(), creator.key().as_ref(), &card_id.to_le_bytes()],
         bump
     )]
     pub gift_card: Account<'info, GiftCard>,
@@ -22,6 +40,7 @@ pub struct CreateGiftCard<'info> {
         mut,
         seeds = [b"config".as_ref()],
         bump = config.bump,
+        constraint = !config.is_paused(PAUSE_CREATION, clock.unix_timestamp) @ GiftProtocolError::CreationPaused,
     )]
     pub config: Account<'info, Config>,
 
@@ -33,12 +52,55 @@ pub struct CreateGiftCard<'info> {
     )]
     pub treasury: Account<'info, Treasury>,
 
+    #[account(
+        seeds = [b"theme_registry".as_ref()],
+        bump = theme_registry.bump,
+    )]
+    pub theme_registry: Account<'info, ThemeRegistry>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + std::mem::size_of::<CreatorStats>(),
+        seeds = [b"creator_stats".as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    /// Vanity code naming the referrer, as an alternative to passing the referrer's pubkey
+    #[account(
+        seeds = [b"referral_code".as_ref(), referral_code.code.as_bytes()],
+        bump = referral_code.bump,
+    )]
+    pub referral_code: Option<Account<'info, ReferralCode>>,
+
+    /// Promo campaign to apply, if any
+    #[account(
+        mut,
+        seeds = [b"promo".as_ref(), &promo_campaign.promo_id.to_le_bytes()],
+        bump = promo_campaign.bump,
+    )]
+    pub promo_campaign: Option<Account<'info, PromoCampaign>>,
+
+    /// The creator's spend against the promo campaign, required with a promo campaign
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = 8 + std::mem::size_of::<PromoUsage>(),
+        seeds = [
+            b"promo_usage".as_ref(),
+            promo_campaign.as_ref().ok_or(GiftProtocolError::InvalidInstruction)?.key().as_ref(),
+            creator.key().as_ref(),
+        ],
+        bump
+    )]
+    pub promo_usage: Option<Account<'info, PromoUsage>>,
+
     /// The referrer account is optional and only required if a referrer is provided
     #[account(
         mut,
-        seeds = [b"referral".as_ref(), referrer.unwrap_or_default().as_ref()],
-        bump,
-        constraint = referrer.is_none() || referral.owner == referrer.unwrap() @ GiftProtocolError::InvalidReferrer
+        seeds = [b"referral".as_ref(), referral.owner.as_ref()],
+        bump = referral.bump,
     )]
     pub referral: Option<Account<'info, Referral>>,
 
@@ -46,26 +108,115 @@ pub struct CreateGiftCard<'info> {
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(
-    ctx: Context<CreateGiftCard>,
-    amount: u64,
-    recipient: Pubkey,
-    expiry_time: i64,
-    message: String,
-    referrer: Option<Pubkey>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateGiftCard<'info>>,
+    card_id: u64,
+    params: GiftCardParams,
 ) -> Result<()> {
+    let GiftCardParams {
+        amount,
+        recipient,
+        expiry_time,
+        message,
+        referrer,
+        theme_id,
+        metadata_uri,
+        content_hash,
+        merchants,
+    } = params;
+
+    // A vanity code stands in for the referrer's pubkey
+    let referrer = match ctx.accounts.referral_code.as_ref() {
+        Some(code) => {
+            require!(referrer.is_none_or(|key| key == code.owner), GiftProtocolError::InvalidReferrer);
+            Some(code.owner)
+        }
+        None => referrer,
+    };
+    if let Some(referral) = ctx.accounts.referral.as_ref() {
+        require!(referrer == Some(referral.owner), GiftProtocolError::InvalidReferrer);
+    }
+
+    // Promo usage is tracked per campaign, so the two accounts come together or not at all
+    require!(
+        ctx.accounts.promo_usage.is_some() == ctx.accounts.promo_campaign.is_some(),
+        GiftProtocolError::InvalidInstruction
+    );
+
     // Validate inputs
     require!(amount > 0, GiftProtocolError::InvalidAmount);
+    require!(referrer != Some(ctx.accounts.creator.key()), GiftProtocolError::SelfReferral);
     require!(message.len() <= 280, GiftProtocolError::MessageTooLong); // 280 chars max
+    require!(
+        metadata_uri.as_ref().is_none_or(|uri| uri.len() <= MAX_METADATA_URI_LEN),
+        GiftProtocolError::UriTooLong
+    );
+    require!(merchants.len() <= MAX_CARD_MERCHANTS, GiftProtocolError::TooManyMerchants);
     
+    // The theme must be registered and active
+    let theme = ctx.accounts.theme_registry.themes
+        .get(theme_id as usize)
+        .ok_or(GiftProtocolError::InvalidTheme)?;
+    require!(theme.is_active, GiftProtocolError::ThemeInactive);
+
+    // Premium themes carry a surcharge on top of the card amount
+    let premium_amount = (amount as u128)
+        .checked_mul(theme.premium_bps as u128)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        / 10000;
+    let premium_amount = u64::try_from(premium_amount).map_err(|_| ProgramError::ArithmeticOverflow)?;
+
+    // Enforce the creator's velocity limits
+    let creator_stats = &mut ctx.accounts.creator_stats;
+    if creator_stats.creator == Pubkey::default() {
+        creator_stats.creator = ctx.accounts.creator.key();
+        creator_stats.bump = *ctx.bumps.get("creator_stats").unwrap();
+    }
+    creator_stats.record_issuance(&ctx.accounts.config, amount, ctx.accounts.clock.unix_timestamp)?;
+
     // If expiry time is provided, ensure it's in the future
     if expiry_time > 0 {
         let current_time = ctx.accounts.clock.unix_timestamp;
         require!(expiry_time > current_time, GiftProtocolError::Expired);
     }
 
-    // Calculate commission amount (commission_rate is in basis points, 10000 = 100%)
-    let commission_rate = ctx.accounts.config.commission_rate;
+    // Commission and direct referral rates, in basis points (10000 = 100%)
+    let has_referrer = referrer.is_some() && ctx.accounts.referral.is_some();
+    let mut commission_rate = ctx.accounts.config.commission_rate;
+    let mut referral_rate = ctx.accounts.config.referral_rate;
+
+    // An active promo campaign swaps in its rates while its budgets cover the cost
+    if let Some(promo) = ctx.accounts.promo_campaign.as_mut() {
+        let usage = ctx.accounts.promo_usage.as_mut().ok_or(GiftProtocolError::InvalidInstruction)?;
+        if usage.user == Pubkey::default() {
+            usage.promo = promo.key();
+            usage.user = ctx.accounts.creator.key();
+            usage.bump = *ctx.bumps.get("promo_usage").unwrap();
+        }
+
+        // Rates may have changed since the promo was created; one that no longer fits is skipped
+        let promo_rates = promo.rates(&ctx.accounts.config)
+            .filter(|_| promo.is_active(ctx.accounts.clock.unix_timestamp));
+        if let Some((promo_commission_rate, promo_referral_rate)) = promo_rates {
+            // Cost is the commission given up plus any referral reward above the usual rate
+            let commission_discount = (amount * commission_rate) / 10000 - (amount * promo_commission_rate) / 10000;
+            let extra_referral = if has_referrer {
+                ((amount * promo_referral_rate) / 10000).saturating_sub((amount * referral_rate) / 10000)
+            } else {
+                0
+            };
+
+            if promo.try_spend(usage, commission_discount + extra_referral) {
+                commission_rate = promo_commission_rate;
+                referral_rate = promo_referral_rate;
+                msg!("Promo {} applied", promo.promo_id);
+            } else {
+                msg!("Promo {} budget exhausted", promo.promo_id);
+            }
+        }
+    }
+
+    // Calculate commission amount
     let commission_amount = (amount * commission_rate) / 10000;
 
     // Calculate the gift card amount after commission
@@ -73,27 +224,65 @@ pub fn handler(
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
     // Calculate referral amount if a valid referrer is provided
-    let (treasury_amount, referral_amount) = if referrer.is_some() && ctx.accounts.referral.is_some() {
-        let referral_rate = ctx.accounts.config.referral_rate;
+    let (treasury_amount, referral_amount) = if has_referrer {
         let referral_amount = (amount * referral_rate) / 10000;
         
         // Remainder goes to treasury
-        (commission_amount - referral_amount, referral_amount)
+        let treasury_amount = commission_amount.checked_sub(referral_amount)
+            .ok_or(GiftProtocolError::InvalidReferralRate)?;
+        (treasury_amount, referral_amount)
     } else {
         // All commission goes to treasury
         (commission_amount, 0)
     };
 
+    // Walk the direct referrer's upline, one remaining account per level, paying each tier's rate
+    let mut upline: Vec<(Account<'info, Referral>, u64)> = Vec::new();
+    if let Some(direct) = ctx.accounts.referral.as_ref().filter(|_| referrer.is_some()) {
+        let mut visited = vec![ctx.accounts.creator.key(), direct.owner];
+        let mut next = direct.referred_by;
+        let mut upline_accounts = ctx.remaining_accounts.iter();
+
+        for &tier_rate in ctx.accounts.config.referral_tiers[1..].iter() {
+            let upline_owner = match next {
+                Some(owner) if tier_rate > 0 => owner,
+                _ => break,
+            };
+            require!(!visited.contains(&upline_owner), GiftProtocolError::ReferralCycle);
+
+            let upline_info = upline_accounts.next().ok_or(GiftProtocolError::InvalidReferrer)?;
+            let (expected, _) = Pubkey::find_program_address(&[b"referral".as_ref(), upline_owner.as_ref()], ctx.program_id);
+            require_keys_eq!(upline_info.key(), expected, GiftProtocolError::InvalidReferrer);
+            let upline_referral: Account<'info, Referral> = Account::try_from(upline_info)?;
+
+            visited.push(upline_owner);
+            next = upline_referral.referred_by;
+            upline.push((upline_referral, (amount * tier_rate) / 10000));
+        }
+    }
+    let upline_amount: u64 = upline.iter().map(|(_, tier_amount)| tier_amount).sum();
+    let treasury_amount = treasury_amount.checked_sub(upline_amount)
+        .ok_or(GiftProtocolError::InvalidReferralRate)?;
+
     // Create gift card data
     let gift_card = &mut ctx.accounts.gift_card;
     gift_card.creator = ctx.accounts.creator.key();
+    gift_card.card_id = card_id;
     gift_card.recipient = recipient;
     gift_card.amount = gift_amount;
     gift_card.is_redeemed = false;
     gift_card.expiry_time = expiry_time;
     gift_card.message = message;
+    gift_card.encrypted_message = None;
     gift_card.referrer = referrer;
+    gift_card.theme_id = theme_id;
+    gift_card.metadata_uri = metadata_uri;
+    gift_card.content_hash = content_hash;
+    gift_card.nft_mint = None;
+    gift_card.merchants = merchants;
     gift_card.bump = *ctx.bumps.get("gift_card").unwrap();
+    gift_card.breakage_policy = ctx.accounts.config.breakage_policy;
+    gift_card.breakage_split_bps = ctx.accounts.config.breakage_split_bps;
 
     // Update config stats
     let config = &mut ctx.accounts.config;
@@ -102,14 +291,16 @@ pub fn handler(
     config.total_gift_cards = config.total_gift_cards.checked_add(1)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
-    if referral_amount > 0 {
-        config.total_referral_payouts = config.total_referral_payouts.checked_add(referral_amount)
+    if referral_amount + upline_amount > 0 {
+        config.total_referral_payouts = config.total_referral_payouts.checked_add(referral_amount + upline_amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
     }
 
     // Update treasury
     let treasury = &mut ctx.accounts.treasury;
     treasury.balance = treasury.balance.checked_add(treasury_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?
+        .checked_add(premium_amount)
         .ok_or(ProgramError::ArithmeticOverflow)?;
 
     // Transfer the full amount from creator to the system program (temp account)
@@ -132,14 +323,25 @@ pub fn handler(
         system_program::transfer(cpi_ctx, treasury_amount)?;
     }
 
-    // If referral is provided, transfer referral amount
+    // Transfer the theme premium to the treasury
+    if premium_amount > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.creator.to_account_info(),
+            to: treasury.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        system_program::transfer(cpi_ctx, premium_amount)?;
+    }
+
+    // If referral is provided, credit the referral amount to the referrer's vault
     if referral_amount > 0 && ctx.accounts.referral.is_some() {
         let referral = ctx.accounts.referral.as_mut().unwrap();
 
-        // Transfer to the referrer
+        // Hold the commission in the referral account until the owner claims it
         let cpi_accounts = system_program::Transfer {
             from: ctx.accounts.creator.to_account_info(),
-            to: ctx.accounts.creator.to_account_info(), // This will be the referrer's account in production
+            to: referral.to_account_info(),
         };
         let cpi_program = ctx.accounts.system_program.to_account_info();
         let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
@@ -148,12 +350,35 @@ pub fn handler(
         // Update referral stats
         referral.total_earned = referral.total_earned.checked_add(referral_amount)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        referral.unclaimed_earnings = referral.unclaimed_earnings.checked_add(referral_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
         referral.referral_count = referral.referral_count.checked_add(1)
             .ok_or(ProgramError::ArithmeticOverflow)?;
     }
 
+    // Credit each upline level's vault
+    for (mut upline_referral, tier_amount) in upline {
+        if tier_amount == 0 {
+            continue;
+        }
+
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.creator.to_account_info(),
+            to: upline_referral.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.system_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        system_program::transfer(cpi_ctx, tier_amount)?;
+
+        upline_referral.total_earned = upline_referral.total_earned.checked_add(tier_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        upline_referral.unclaimed_earnings = upline_referral.unclaimed_earnings.checked_add(tier_amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        upline_referral.exit(ctx.program_id)?;
+    }
+
     msg!("Gift card created: {} lamports ({}% commission, {}% referral)",
-        gift_amount, commission_rate as f64 / 100.0, ctx.accounts.config.referral_rate as f64 / 100.0);
+        gift_amount, commission_rate as f64 / 100.0, referral_rate as f64 / 100.0);
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+// Terms of a promo campaign
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PromoParams {
+    // Window the promo applies in
+    pub start_time: i64,
+    pub end_time: i64,
+
+    // Direct referral rate used instead of the config rate, in basis points
+    pub referral_rate_override: Option<u64>,
+
+    // Taken off the commission rate, in basis points
+    pub commission_discount_bps: u64,
+
+    // Total cost the promo may absorb, and the cap per creator (0 for none)
+    pub budget: u64,
+    pub per_user_cap: u64,
+}
+
+#[derive(Accounts)]
+#[instruction(promo_id: u64)]
+pub struct CreatePromoCampaign<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + std::mem::size_of::<PromoCampaign>(),
+        seeds = [b"promo".as_ref(), &promo_id.to_le_bytes()],
+        bump
+    )]
+    pub promo_campaign: Account<'info, PromoCampaign>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CreatePromoCampaign>, promo_id: u64, params: PromoParams) -> Result<()> {
+    require!(params.end_time > params.start_time, GiftProtocolError::InvalidPromoCampaign);
+    require!(params.commission_discount_bps <= 10000, GiftProtocolError::InvalidCommissionRate);
+
+    let promo = &mut ctx.accounts.promo_campaign;
+    promo.promo_id = promo_id;
+    promo.start_time = params.start_time;
+    promo.end_time = params.end_time;
+    promo.referral_rate_override = params.referral_rate_override;
+    promo.commission_discount_bps = params.commission_discount_bps;
+    promo.budget = params.budget;
+    promo.spent = 0;
+    promo.per_user_cap = params.per_user_cap;
+    promo.bump = *ctx.bumps.get("promo_campaign").unwrap();
+
+    // The boosted referral rate has to fit within the discounted commission
+    require!(promo.rates(&ctx.accounts.config).is_some(), GiftProtocolError::InvalidReferralRate);
+
+    msg!("Promo {} created: {} to {}, budget {}", promo_id, params.start_time, params.end_time, params.budget);
+
+    Ok(())
+}
\ No newline at end of file
@@ -4,7 +4,7 @@ use crate::state::*;
 use crate::errors::*;
 
 #[derive(Accounts)]
-#[instruction(title: String, description: String, choices: Vec<String>, voting_end_time: i64)]
+#[instruction(title: String, description: String, choices: Vec<String>, voting_end_time: i64, actions: Vec<Vec<ProposalAction>>)]
 pub struct CreateProposal<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -14,6 +14,7 @@ pub struct CreateProposal<'info> {
         seeds = [b"config".as_ref()],
         bump = config.bump,
         constraint = config.governance_token_mint.is_some() @ GiftProtocolError::InvalidTokenAuthority,
+        constraint = !config.is_paused(PAUSE_GOVERNANCE, clock.unix_timestamp) @ GiftProtocolError::GovernancePaused,
     )]
     pub config: Account<'info, Config>,
 
@@ -32,7 +33,8 @@ pub struct CreateProposal<'info> {
                 title.len() + description.len() + 
                 choices.iter().map(|c| c.len()).sum::<usize>() + 
                 (choices.len() * 4) + // Vec<String> overhead
-                (choices.len() * 8),  // Vec<u64> for vote counts
+                (choices.len() * 8) + // Vec<u64> for vote counts
+                actions.try_to_vec().map_or(0, |data| data.len()),
         seeds = [
             b"proposal".as_ref(), 
             &(config.total_gift_cards).to_le_bytes()
@@ -50,12 +52,18 @@ pub fn handler(
     title: String, 
     description: String, 
     choices: Vec<String>,
-    voting_end_time: i64
+    voting_end_time: i64,
+    actions: Vec<Vec<ProposalAction>>,
 ) -> Result<()> {
     // Validate inputs
     require!(!title.is_empty(), GiftProtocolError::InvalidInstruction);
     require!(!description.is_empty(), GiftProtocolError::InvalidInstruction);
     require!(choices.len() >= 2 && choices.len() <= 10, GiftProtocolError::TooManyChoices);
+    require!(actions.is_empty() || actions.len() == choices.len(), GiftProtocolError::InvalidInstruction);
+    require!(
+        actions.iter().all(|choice_actions| choice_actions.len() <= MAX_PROPOSAL_ACTIONS),
+        GiftProtocolError::TooManyActions
+    );
     
     // Ensure voting end time is in the future
     let current_time = ctx.accounts.clock.unix_timestamp;
@@ -75,11 +83,13 @@ pub fn handler(
     proposal.vote_counts = vec![0; choices.len()];
     
     proposal.voting_end_time = voting_end_time;
-    proposal.is_finalized = false;
+    proposal.state = ProposalState::Draft;
     proposal.winning_choice = None;
     proposal.total_votes = 0;
     proposal.created_at = current_time;
     proposal.proposal_id = config.total_gift_cards; // Use gift card count as a unique ID source
+    proposal.actions = actions;
+    proposal.finalized_at = 0;
     proposal.bump = bump;
     
     // Increment protocol stats
@@ -4,6 +4,7 @@ use crate::state::*;
 use crate::errors::*;
 
 #[derive(Accounts)]
+#[instruction(referred_by: Option<Pubkey>)]
 pub struct CreateReferral<'info> {
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -17,17 +18,33 @@ pub struct CreateReferral<'info> {
     )]
     pub referral: Account<'info, Referral>,
 
+    /// Referral code of the user who referred the owner, required when referred_by is set
+    #[account(
+        seeds = [b"referral".as_ref(), referred_by.unwrap_or_default().as_ref()],
+        bump = upline_referral.bump,
+    )]
+    pub upline_referral: Option<Account<'info, Referral>>,
+
     pub system_program: Program<'info, System>,
     pub clock: Sysvar<'info, Clock>,
 }
 
-pub fn handler(ctx: Context<CreateReferral>) -> Result<()> {
+pub fn handler(ctx: Context<CreateReferral>, referred_by: Option<Pubkey>) -> Result<()> {
+    // The upline must already have a referral code, so chains can never loop back
+    if let Some(upline) = referred_by {
+        require_keys_neq!(upline, ctx.accounts.owner.key(), GiftProtocolError::SelfReferral);
+        require!(ctx.accounts.upline_referral.is_some(), GiftProtocolError::InvalidReferrer);
+    }
+
     let referral = &mut ctx.accounts.referral;
     let bump = *ctx.bumps.get("referral").unwrap();
     
     // Initialize referral data
     referral.owner = ctx.accounts.owner.key();
+    referral.referred_by = referred_by;
     referral.total_earned = 0;
+    referral.unclaimed_earnings = 0;
+    referral.total_claimed = 0;
     referral.referral_count = 0;
     referral.created_at = ctx.accounts.clock.unix_timestamp;
     referral.bump = bump;
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct DelistGiftCard<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"listing".as_ref(), listing.gift_card.as_ref()],
+        bump = listing.bump,
+        constraint = listing.seller == seller.key() @ GiftProtocolError::NotAuthorized
+    )]
+    pub listing: Account<'info, Listing>,
+}
+
+pub fn handler(ctx: Context<DelistGiftCard>) -> Result<()> {
+    msg!("Gift card {} delisted", ctx.accounts.listing.gift_card);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,284 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    /// Anyone may execute a finalized proposal; pays rent for accounts its actions create
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_GOVERNANCE, clock.unix_timestamp) @ GiftProtocolError::GovernancePaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"proposal".as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = proposal.state != ProposalState::Executed @ GiftProtocolError::ProposalAlreadyExecuted,
+        constraint = proposal.state == ProposalState::Succeeded @ GiftProtocolError::ProposalNotApproved,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump = treasury.bump,
+        constraint = treasury.key() == config.treasury @ GiftProtocolError::TreasuryMismatch
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Required when the winning choice adds a theme
+    #[account(
+        mut,
+        seeds = [b"theme_registry".as_ref()],
+        bump = theme_registry.bump,
+    )]
+    pub theme_registry: Option<Account<'info, ThemeRegistry>>,
+
+    /// Required when the winning choice spends SPL tokens
+    pub token_program: Option<Program<'info, Token>>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+    // Remaining accounts, consumed in action order:
+    //   TreasurySpend (SOL): recipient
+    //   TreasurySpend (SPL): treasury token account, recipient token account
+    //   AddMerchant: merchant PDA
+}
+
+pub fn handler<'info>(mut ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
+    let proposal = &ctx.accounts.proposal;
+    let winner = proposal.winning_choice.ok_or(GiftProtocolError::ProposalNotApproved)?;
+    let actions = proposal.actions.get(winner as usize).cloned().unwrap_or_default();
+    require!(!actions.is_empty(), GiftProtocolError::ProposalNotApproved);
+
+    // Give token holders time to react between the vote and its effect
+    require!(
+        ctx.accounts.clock.unix_timestamp >= proposal.finalized_at.saturating_add(PROPOSAL_TIMELOCK),
+        GiftProtocolError::TimelockActive
+    );
+
+    // Executed exactly once
+    ctx.accounts.proposal.state = ProposalState::Executed;
+
+    let mut action_accounts = ctx.remaining_accounts.iter();
+    for action in actions {
+        match action {
+            ProposalAction::TreasurySpend { recipient, amount, mint } => {
+                treasury_spend(&mut ctx, &mut action_accounts, recipient, amount, mint)?;
+            }
+            ProposalAction::SetRates { commission_rate, referral_rate } => {
+                let config = &mut ctx.accounts.config;
+                let mut tiers = config.referral_tiers;
+                tiers[0] = referral_rate;
+                Config::validate_rates(commission_rate, &tiers)?;
+
+                emit!(ConfigUpdated {
+                    old_commission_rate: config.commission_rate,
+                    new_commission_rate: commission_rate,
+                    old_referral_rate: config.referral_rate,
+                    new_referral_rate: referral_rate,
+                    old_settlement_fee_bps: config.settlement_fee_bps,
+                    new_settlement_fee_bps: config.settlement_fee_bps,
+                    old_marketplace_fee_bps: config.marketplace_fee_bps,
+                    new_marketplace_fee_bps: config.marketplace_fee_bps,
+                });
+
+                config.commission_rate = commission_rate;
+                config.referral_rate = referral_rate;
+                config.referral_tiers[0] = referral_rate;
+                msg!("Rates set: commission {}bp, referral {}bp", commission_rate, referral_rate);
+            }
+            ProposalAction::SetPauseFlags { flags } => {
+                ctx.accounts.config.set_pause_flags(flags, ctx.accounts.clock.unix_timestamp)?;
+                msg!("Pause flags set to {:#06b}", flags);
+            }
+            ProposalAction::AddMerchant { owner, name, settlement } => {
+                let merchant_info = action_accounts.next().ok_or(GiftProtocolError::InvalidInstruction)?;
+                add_merchant(&ctx, merchant_info, owner, name, settlement)?;
+            }
+            ProposalAction::AddTheme { name, artwork_uri, premium_bps } => {
+                require!(!name.is_empty() && name.len() <= MAX_THEME_NAME_LEN, GiftProtocolError::NameTooLong);
+                require!(artwork_uri.len() <= MAX_METADATA_URI_LEN, GiftProtocolError::UriTooLong);
+                require!(premium_bps <= 10000, GiftProtocolError::InvalidCommissionRate);
+
+                let theme_registry = ctx.accounts.theme_registry.as_mut().ok_or(GiftProtocolError::InvalidTheme)?;
+                require!(theme_registry.themes.len() < MAX_THEMES, GiftProtocolError::ThemeRegistryFull);
+                theme_registry.themes.push(Theme {
+                    name,
+                    artwork_uri,
+                    is_active: true,
+                    premium_bps,
+                });
+                msg!("Theme {} added with {}bp premium", theme_registry.themes.len() - 1, premium_bps);
+            }
+            ProposalAction::SetBreakagePolicy { policy, split_bps } => {
+                let config = &mut ctx.accounts.config;
+                config.set_breakage_policy(policy, split_bps)?;
+                msg!("Breakage policy set to {} ({}bp to treasury)", config.breakage_policy, config.breakage_split_bps);
+            }
+        }
+    }
+
+    msg!("Proposal {} executed", ctx.accounts.proposal.proposal_id);
+
+    Ok(())
+}
+
+// Pay out of the treasury in SOL or an SPL mint
+fn treasury_spend<'info>(
+    ctx: &mut Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    action_accounts: &mut std::slice::Iter<'info, AccountInfo<'info>>,
+    recipient: Pubkey,
+    amount: u64,
+    mint: Option<Pubkey>,
+) -> Result<()> {
+    require!(amount > 0, GiftProtocolError::InvalidAmount);
+
+    match mint {
+        Some(mint) => {
+            let treasury_token_info = action_accounts.next().ok_or(GiftProtocolError::InvalidPaymentAccount)?;
+            let recipient_token_info = action_accounts.next().ok_or(GiftProtocolError::InvalidPaymentAccount)?;
+            let token_program = ctx.accounts.token_program.as_ref().ok_or(GiftProtocolError::InvalidPaymentAccount)?;
+            let treasury_token_account: Account<'info, TokenAccount> = Account::try_from(treasury_token_info)?;
+            let recipient_token_account: Account<'info, TokenAccount> = Account::try_from(recipient_token_info)?;
+            require_keys_eq!(treasury_token_account.mint, mint, GiftProtocolError::InvalidPaymentAccount);
+            require_keys_eq!(treasury_token_account.owner, ctx.accounts.treasury.key(), GiftProtocolError::InvalidPaymentAccount);
+            require_keys_eq!(recipient_token_account.mint, mint, GiftProtocolError::InvalidPaymentAccount);
+            require_keys_eq!(recipient_token_account.owner, recipient, GiftProtocolError::InvalidPaymentAccount);
+            require!(treasury_token_account.amount >= amount, GiftProtocolError::InsufficientFunds);
+
+            let treasury_signer_seeds = &[
+                b"treasury".as_ref(),
+                &[ctx.accounts.treasury.bump],
+            ];
+            let treasury_signer = &[&treasury_signer_seeds[..]];
+
+            let cpi_accounts = Transfer {
+                from: treasury_token_info.clone(),
+                to: recipient_token_info.clone(),
+                authority: ctx.accounts.treasury.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, treasury_signer);
+            token::transfer(cpi_ctx, amount)?;
+        }
+        None => {
+            let recipient_info = action_accounts.next().ok_or(GiftProtocolError::InvalidPaymentAccount)?;
+            require_keys_eq!(recipient_info.key(), recipient, GiftProtocolError::InvalidPaymentAccount);
+
+            // Treasury.balance excludes the account's rent, so it bounds what can leave
+            let treasury = &mut ctx.accounts.treasury;
+            treasury.balance = treasury.balance.checked_sub(amount)
+                .ok_or(GiftProtocolError::InsufficientFunds)?;
+
+            let treasury_info = treasury.to_account_info();
+
+            **treasury_info.lamports.borrow_mut() = treasury_info.lamports()
+                .checked_sub(amount)
+                .ok_or(GiftProtocolError::InsufficientFunds)?;
+            **recipient_info.lamports.borrow_mut() = recipient_info.lamports()
+                .checked_add(amount)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+    }
+
+    emit!(TreasurySpent {
+        proposal: ctx.accounts.proposal.key(),
+        recipient,
+        amount,
+        mint,
+    });
+
+    msg!("Treasury spent {} to {}", amount, recipient);
+
+    Ok(())
+}
+
+// Create and populate a merchant PDA, as register_merchant would
+fn add_merchant<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>,
+    merchant_info: &'info AccountInfo<'info>,
+    owner: Pubkey,
+    name: String,
+    settlement: Pubkey,
+) -> Result<()> {
+    require!(!name.is_empty() && name.len() <= MAX_MERCHANT_NAME_LEN, GiftProtocolError::NameTooLong);
+
+    let (expected, bump) = Pubkey::find_program_address(&[b"merchant".as_ref(), owner.as_ref()], ctx.program_id);
+    require_keys_eq!(merchant_info.key(), expected, GiftProtocolError::InvalidInstruction);
+
+    let merchant_signer_seeds = &[
+        b"merchant".as_ref(),
+        owner.as_ref(),
+        &[bump],
+    ];
+    let merchant_signer = &[&merchant_signer_seeds[..]];
+
+    // Like Anchor's init: lamports already sent to the PDA can't stop it being created
+    let rent_exempt = Rent::get()?.minimum_balance(Merchant::LEN);
+    let current_lamports = merchant_info.lamports();
+    if current_lamports == 0 {
+        let cpi_accounts = system_program::CreateAccount {
+            from: ctx.accounts.executor.to_account_info(),
+            to: merchant_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.system_program.to_account_info(), cpi_accounts, merchant_signer);
+        system_program::create_account(cpi_ctx, rent_exempt, Merchant::LEN as u64, ctx.program_id)?;
+    } else {
+        let top_up = rent_exempt.saturating_sub(current_lamports);
+        if top_up > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.executor.to_account_info(),
+                to: merchant_info.clone(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, top_up)?;
+        }
+
+        let cpi_accounts = system_program::Allocate {
+            account_to_allocate: merchant_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.system_program.to_account_info(), cpi_accounts, merchant_signer);
+        system_program::allocate(cpi_ctx, Merchant::LEN as u64)?;
+
+        let cpi_accounts = system_program::Assign {
+            account_to_assign: merchant_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.system_program.to_account_info(), cpi_accounts, merchant_signer);
+        system_program::assign(cpi_ctx, ctx.program_id)?;
+    }
+
+    let merchant = Merchant {
+        owner,
+        settlement,
+        name,
+        is_active: true,
+        approved_by_proposal: Some(ctx.accounts.proposal.key()),
+        total_received: 0,
+        total_fees: 0,
+        payment_count: 0,
+        registered_at: ctx.accounts.clock.unix_timestamp,
+        bump,
+    };
+    merchant.try_serialize(&mut &mut merchant_info.try_borrow_mut_data()?[..])?;
+
+    emit!(MerchantRegistered {
+        merchant: merchant_info.key(),
+        owner,
+        settlement,
+        approved_by_proposal: merchant.approved_by_proposal,
+    });
+
+    msg!("Merchant {} registered for {}", merchant.name, owner);
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
 use crate::state::*;
 use crate::errors::*;
 
@@ -7,12 +8,25 @@ pub struct FinalizeProposal<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_GOVERNANCE, clock.unix_timestamp) @ GiftProtocolError::GovernancePaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Governance token mint, whose supply the quorum is measured against
+    #[account(
+        address = config.governance_token_mint.unwrap_or_default() @ GiftProtocolError::InvalidTokenAuthority,
+    )]
+    pub governance_mint: Account<'info, Mint>,
+
     #[account(
         mut,
         seeds = [b"proposal".as_ref(), &proposal.proposal_id.to_le_bytes()],
         bump = proposal.bump,
-        constraint = !proposal.is_finalized @ GiftProtocolError::ProposalActive,
-        constraint = proposal.voting_end_time <= clock.unix_timestamp @ GiftProtocolError::ProposalVotingEnded,
+        constraint = proposal.state == ProposalState::Active @ GiftProtocolError::ProposalNotActive,
+        constraint = proposal.voting_end_time <= clock.unix_timestamp @ GiftProtocolError::ProposalActive,
     )]
     pub proposal: Account<'info, Proposal>,
 
@@ -20,33 +34,23 @@ pub struct FinalizeProposal<'info> {
 }
 
 pub fn handler(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let supply = ctx.accounts.governance_mint.supply;
+
     // Get the proposal account
     let proposal = &mut ctx.accounts.proposal;
     
-    // Determine the winning choice (the one with the most votes)
-    let vote_counts = &proposal.vote_counts;
-    let mut winning_choice: Option<u8> = None;
-    let mut max_votes: u64 = 0;
-    
-    for (i, &votes) in vote_counts.iter().enumerate() {
-        if votes > max_votes {
-            max_votes = votes;
-            winning_choice = Some(i as u8);
-        }
-    }
-    
-    // Set the winner and mark the proposal as finalized
-    proposal.winning_choice = winning_choice;
-    proposal.is_finalized = true;
+    // Tally the votes against the quorum and approval threshold
+    proposal.finalize(config, supply, ctx.accounts.clock.unix_timestamp);
     
-    msg!("Proposal {} finalized", proposal.proposal_id);
+    msg!("Proposal {} finalized: {:?}", proposal.proposal_id, proposal.state);
     
-    if let Some(winner) = winning_choice {
+    if let Some(winner) = proposal.winning_choice {
         msg!("Winning choice: {} with {} votes", 
             proposal.choices[winner as usize],
-            vote_counts[winner as usize]);
+            proposal.vote_counts[winner as usize]);
     } else {
-        msg!("No votes were cast on this proposal");
+        msg!("No choice met quorum and threshold ({} of {} supply voted)", proposal.total_votes, supply);
     }
     
     Ok(())
@@ -32,7 +32,9 @@ pub struct Initialize<'info> {
 pub fn handler(
     ctx: Context<Initialize>, 
     commission_rate: u64, 
-    referral_rate: u64
+    referral_rate: u64,
+    settlement_fee_bps: u64,
+    marketplace_fee_bps: u64,
 ) -> Result<()> {
     // Validate commission rate (0-100% in basis points)
     require!(
@@ -46,6 +48,18 @@ pub fn handler(
         GiftProtocolError::InvalidReferralRate
     );
 
+    // Validate settlement fee (0-100% in basis points)
+    require!(
+        settlement_fee_bps <= 10000,
+        GiftProtocolError::InvalidCommissionRate
+    );
+
+    // Validate marketplace fee (0-100% in basis points)
+    require!(
+        marketplace_fee_bps <= 10000,
+        GiftProtocolError::InvalidCommissionRate
+    );
+
     // Get the bump seeds
     let config_bump = *ctx.bumps.get("config").unwrap();
     let treasury_bump = *ctx.bumps.get("treasury").unwrap();
@@ -53,14 +67,34 @@ pub fn handler(
     // Initialize config account
     let config = &mut ctx.accounts.config;
     config.authority = ctx.accounts.authority.key();
+    config.pending_authority = None;
     config.commission_rate = commission_rate;
     config.referral_rate = referral_rate;
+    config.referral_tiers = [0; MAX_REFERRAL_TIERS];
+    config.referral_tiers[0] = referral_rate;
+    config.settlement_fee_bps = settlement_fee_bps;
+    config.marketplace_fee_bps = marketplace_fee_bps;
     config.treasury = ctx.accounts.treasury.key();
     config.total_commission = 0;
     config.total_referral_payouts = 0;
+    config.total_referral_claimed = 0;
     config.total_gift_cards = 0;
     config.total_staked = 0;
     config.governance_token_mint = None;
+    config.pauser = ctx.accounts.authority.key();
+    config.pause_flags = 0;
+    config.redemption_paused_at = 0;
+    config.expiry_grace_period = DEFAULT_EXPIRY_GRACE_PERIOD;
+    config.crank_bounty = DEFAULT_CRANK_BOUNTY;
+    config.breakage_policy = BREAKAGE_REFUND;
+    config.breakage_split_bps = 0;
+    config.total_breakage = 0;
+    config.quorum_bps = DEFAULT_QUORUM_BPS;
+    config.approval_threshold_bps = DEFAULT_APPROVAL_THRESHOLD_BPS;
+    config.max_card_amount = 0;
+    config.max_daily_volume = 0;
+    config.max_daily_cards = 0;
+    config.max_monthly_volume = 0;
     config.bump = config_bump;
 
     // Initialize treasury account
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct InitializeThemeRegistry<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = ThemeRegistry::LEN,
+        seeds = [b"theme_registry".as_ref()],
+        bump
+    )]
+    pub theme_registry: Account<'info, ThemeRegistry>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeThemeRegistry>) -> Result<()> {
+    let theme_registry = &mut ctx.accounts.theme_registry;
+    theme_registry.themes = Vec::new();
+    theme_registry.bump = *ctx.bumps.get("theme_registry").unwrap();
+
+    msg!("Theme registry initialized");
+
+    Ok(())
+}
\ No newline at end of file
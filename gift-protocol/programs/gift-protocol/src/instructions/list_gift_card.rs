@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ListGiftCard<'info> {
+    #[account(mut)]
+    pub seller: Signer<'info>,
+
+    #[account(
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
+        bump = gift_card.bump,
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed,
+        constraint = gift_card.nft_mint.is_none() @ GiftProtocolError::AlreadyTokenized,
+        constraint = gift_card.recipient == seller.key() @ GiftProtocolError::NotAuthorized
+    )]
+    pub gift_card: Account<'info, GiftCard>,
+
+    #[account(
+        init,
+        payer = seller,
+        space = 8 + std::mem::size_of::<Listing>(),
+        seeds = [b"listing".as_ref(), gift_card.key().as_ref()],
+        bump
+    )]
+    pub listing: Account<'info, Listing>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<ListGiftCard>, price: u64, payment_mint: Option<Pubkey>) -> Result<()> {
+    require!(price > 0, GiftProtocolError::InvalidAmount);
+
+    // Expired cards cannot be listed
+    let gift_card = &ctx.accounts.gift_card;
+    let current_time = ctx.accounts.clock.unix_timestamp;
+    if gift_card.expiry_time > 0 && current_time > gift_card.expiry_time {
+        return Err(GiftProtocolError::Expired.into());
+    }
+
+    let listing = &mut ctx.accounts.listing;
+    listing.seller = ctx.accounts.seller.key();
+    listing.gift_card = gift_card.key();
+    listing.price = price;
+    listing.payment_mint = payment_mint;
+    listing.card_amount = gift_card.amount;
+    listing.created_at = current_time;
+    listing.bump = *ctx.bumps.get("listing").unwrap();
+
+    emit!(GiftCardListed {
+        gift_card: gift_card.key(),
+        seller: listing.seller,
+        price,
+        payment_mint,
+    });
+
+    msg!("Gift card {} listed for {}", gift_card.key(), price);
+
+    Ok(())
+}
\ No newline at end of file
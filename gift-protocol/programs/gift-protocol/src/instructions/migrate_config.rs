@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Config in the pre-upgrade layout, which Account<Config> can't load; data is verified in the handler
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump,
+        owner = crate::ID @ GiftProtocolError::InvalidInstruction,
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let new_len = 8 + std::mem::size_of::<Config>();
+
+    let legacy = {
+        let data = config_info.try_borrow_data()?;
+        require!(data.len() > 8 && data[..8] == Config::DISCRIMINATOR, GiftProtocolError::InvalidInstruction);
+        require!(data.len() < new_len, GiftProtocolError::AlreadyMigrated);
+        LegacyConfig::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(legacy.authority, ctx.accounts.authority.key(), GiftProtocolError::NotAuthorized);
+
+    // The authority pays the rent for the larger account
+    let rent_due = Rent::get()?.minimum_balance(new_len).saturating_sub(config_info.lamports());
+    if rent_due > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: config_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, rent_due)?;
+    }
+    config_info.realloc(new_len, true)?;
+
+    let config = Config::from(legacy);
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    msg!("Config migrated to the current layout ({} bytes)", new_len);
+
+    Ok(())
+}
\ No newline at end of file
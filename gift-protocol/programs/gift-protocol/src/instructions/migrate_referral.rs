@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct MigrateReferral<'info> {
+    /// Anyone may pay to migrate a referral account; it only gains zeroed fields
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Referral in the pre-upgrade layout; owner, address and data are verified in the handler
+    #[account(
+        mut,
+        owner = crate::ID @ GiftProtocolError::InvalidInstruction,
+    )]
+    pub referral: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MigrateReferral>) -> Result<()> {
+    let referral_info = ctx.accounts.referral.to_account_info();
+    let new_len = 8 + std::mem::size_of::<Referral>();
+
+    let legacy = {
+        let data = referral_info.try_borrow_data()?;
+        require!(data.len() > 8 && data[..8] == Referral::DISCRIMINATOR, GiftProtocolError::InvalidInstruction);
+        require!(data.len() < new_len, GiftProtocolError::AlreadyMigrated);
+        LegacyReferral::deserialize(&mut &data[8..])?
+    };
+
+    // The account must sit at its owner's referral address
+    let expected = Pubkey::create_program_address(
+        &[b"referral".as_ref(), legacy.owner.as_ref(), &[legacy.bump]],
+        ctx.program_id,
+    ).map_err(|_| GiftProtocolError::InvalidInstruction)?;
+    require_keys_eq!(referral_info.key(), expected, GiftProtocolError::InvalidInstruction);
+
+    // New fields are appended, so zero-filling the extra space leaves them empty
+    let rent_due = Rent::get()?.minimum_balance(new_len).saturating_sub(referral_info.lamports());
+    if rent_due > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: referral_info.clone(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+        system_program::transfer(cpi_ctx, rent_due)?;
+    }
+    referral_info.realloc(new_len, true)?;
+
+    msg!("Referral account of {} migrated to the current layout", legacy.owner);
+
+    Ok(())
+}
\ No newline at end of file
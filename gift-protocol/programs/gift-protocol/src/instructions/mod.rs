@@ -1,20 +1,100 @@
 pub mod initialize;
+pub mod update_config;
+pub mod migrate_config;
+pub mod migrate_referral;
+pub mod propose_authority;
+pub mod accept_authority;
 pub mod create_gift_card;
 pub mod redeem_gift_card;
+pub mod redeem_gift_card_relayed;
+pub mod redeem_legacy_gift_card;
+pub mod tokenize_gift_card;
+pub mod append_encrypted_message;
+pub mod update_card_metadata;
+pub mod initialize_theme_registry;
+pub mod add_theme;
+pub mod update_theme;
+pub mod register_merchant;
+pub mod update_merchant;
+pub mod pay_merchant;
+pub mod list_gift_card;
+pub mod purchase_gift_card;
+pub mod delist_gift_card;
 pub mod create_referral;
+pub mod claim_referral_earnings;
+pub mod register_referral_code;
+pub mod transfer_referral_code;
+pub mod release_referral_code;
+pub mod set_referral_tiers;
+pub mod create_promo_campaign;
 pub mod create_governance_token;
 pub mod create_proposal;
+pub mod activate_proposal;
+pub mod cancel_proposal;
+pub mod set_governance_params;
 pub mod vote_on_proposal;
+pub mod withdraw_vote_tokens;
 pub mod finalize_proposal;
+pub mod execute_proposal;
 pub mod stake_treasury_funds;
+pub mod set_pause_flags;
+pub mod set_pauser;
+pub mod set_expiry_crank;
+pub mod crank_expired;
+pub mod set_breakage_policy;
+pub mod set_velocity_limits;
+pub mod set_issuer_exemption;
+pub mod create_campaign;
+pub mod claim_campaign;
+pub mod close_campaign;
 
 // Re-export all accounts validation structs
 pub use initialize::*;
+pub use update_config::*;
+pub use migrate_config::*;
+pub use migrate_referral::*;
+pub use propose_authority::*;
+pub use accept_authority::*;
 pub use create_gift_card::*;
 pub use redeem_gift_card::*;
+pub use redeem_gift_card_relayed::*;
+pub use redeem_legacy_gift_card::*;
+pub use tokenize_gift_card::*;
+pub use append_encrypted_message::*;
+pub use update_card_metadata::*;
+pub use initialize_theme_registry::*;
+pub use add_theme::*;
+pub use update_theme::*;
+pub use register_merchant::*;
+pub use update_merchant::*;
+pub use pay_merchant::*;
+pub use list_gift_card::*;
+pub use purchase_gift_card::*;
+pub use delist_gift_card::*;
 pub use create_referral::*;
+pub use claim_referral_earnings::*;
+pub use register_referral_code::*;
+pub use transfer_referral_code::*;
+pub use release_referral_code::*;
+pub use set_referral_tiers::*;
+pub use create_promo_campaign::*;
 pub use create_governance_token::*;
 pub use create_proposal::*;
+pub use activate_proposal::*;
+pub use cancel_proposal::*;
+pub use set_governance_params::*;
 pub use vote_on_proposal::*;
+pub use withdraw_vote_tokens::*;
 pub use finalize_proposal::*;
-pub use stake_treasury_funds::*;
\ No newline at end of file
+pub use execute_proposal::*;
+pub use stake_treasury_funds::*;
+pub use set_pause_flags::*;
+pub use set_pauser::*;
+pub use set_expiry_crank::*;
+pub use crank_expired::*;
+pub use set_breakage_policy::*;
+pub use set_velocity_limits::*;
+pub use set_issuer_exemption::*;
+pub use create_campaign::*;
+pub use claim_campaign::*;
+pub use close_campaign::*;
\ No newline at end of file
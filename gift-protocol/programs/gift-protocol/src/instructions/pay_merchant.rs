@@ -0,0 +1,149 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct PayMerchant<'info> {
+    /// The card's recipient, or the holder of its NFT if the card was tokenized
+    #[account(mut)]
+    pub holder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
+        bump = gift_card.bump,
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed
+    )]
+    pub gift_card: Account<'info, GiftCard>,
+
+    /// The holder's NFT token account, required for tokenized cards
+    #[account(
+        token::authority = holder,
+    )]
+    pub holder_token_account: Option<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [b"merchant".as_ref(), merchant.owner.as_ref()],
+        bump = merchant.bump,
+        constraint = merchant.is_active @ GiftProtocolError::MerchantInactive
+    )]
+    pub merchant: Account<'info, Merchant>,
+
+    /// CHECK: The merchant's settlement account
+    #[account(
+        mut,
+        address = merchant.settlement @ GiftProtocolError::InvalidDestination
+    )]
+    pub settlement: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_REDEMPTION, clock.unix_timestamp) @ GiftProtocolError::RedemptionPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump = treasury.bump,
+        constraint = treasury.key() == config.treasury @ GiftProtocolError::TreasuryMismatch
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<PayMerchant>, amount: u64) -> Result<()> {
+    let gift_card = &mut ctx.accounts.gift_card;
+    let current_time = ctx.accounts.clock.unix_timestamp;
+
+    if gift_card.expiry_time > 0 && current_time > gift_card.expiry_time {
+        return Err(GiftProtocolError::Expired.into());
+    }
+
+    // Tokenized cards are spent by the NFT holder; others by the named recipient
+    match gift_card.nft_mint {
+        Some(nft_mint_key) => {
+            let holder_token_account = ctx.accounts.holder_token_account.as_ref().ok_or(GiftProtocolError::NftRequired)?;
+            require_keys_eq!(holder_token_account.mint, nft_mint_key, GiftProtocolError::InvalidNft);
+            require!(holder_token_account.amount == 1, GiftProtocolError::NftRequired);
+        }
+        None => {
+            require_keys_eq!(gift_card.recipient, ctx.accounts.holder.key(), GiftProtocolError::NotAuthorized);
+        }
+    }
+
+    // Closed-loop cards only pay the merchants they were issued for
+    let merchant_key = ctx.accounts.merchant.key();
+    require!(
+        gift_card.merchants.is_empty() || gift_card.merchants.contains(&merchant_key),
+        GiftProtocolError::MerchantNotAccepted
+    );
+
+    require!(amount > 0, GiftProtocolError::InvalidAmount);
+    require!(amount <= gift_card.amount, GiftProtocolError::InsufficientBalance);
+
+    // Settlement fee (settlement_fee_bps is in basis points, 10000 = 100%)
+    let fee = (amount as u128)
+        .checked_mul(ctx.accounts.config.settlement_fee_bps as u128)
+        .and_then(|fee| fee.checked_div(10000))
+        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+    let merchant_amount = amount.checked_sub(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    gift_card.amount -= amount;
+    let remaining = gift_card.amount;
+
+    let gift_card_info = gift_card.to_account_info();
+    let settlement_info = ctx.accounts.settlement.to_account_info();
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+
+    **settlement_info.lamports.borrow_mut() = settlement_info.lamports()
+        .checked_add(merchant_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **treasury_info.lamports.borrow_mut() = treasury_info.lamports()
+        .checked_add(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **gift_card_info.lamports.borrow_mut() = gift_card_info.lamports()
+        .checked_sub(amount)
+        .ok_or(GiftProtocolError::InsufficientBalance)?;
+
+    // Once the balance is spent the card is done; its rent goes to the holder
+    if remaining == 0 {
+        gift_card.is_redeemed = true;
+
+        let holder_info = ctx.accounts.holder.to_account_info();
+        **holder_info.lamports.borrow_mut() = holder_info.lamports()
+            .checked_add(gift_card_info.lamports())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        **gift_card_info.lamports.borrow_mut() = 0;
+    }
+
+    let treasury = &mut ctx.accounts.treasury;
+    treasury.balance = treasury.balance.checked_add(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    let merchant = &mut ctx.accounts.merchant;
+    merchant.total_received = merchant.total_received.checked_add(amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    merchant.total_fees = merchant.total_fees.checked_add(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    merchant.payment_count = merchant.payment_count.checked_add(1)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    emit!(MerchantPaid {
+        gift_card: gift_card.key(),
+        merchant: merchant_key,
+        amount,
+        fee,
+        remaining,
+    });
+
+    msg!("Paid {} lamports to merchant {} ({} fee, {} remaining)", merchant_amount, merchant.name, fee, remaining);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct ProposeAuthority<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pending_authority = Some(new_authority);
+
+    emit!(AuthorityProposed {
+        authority: config.authority,
+        pending_authority: new_authority,
+    });
+
+    msg!("Authority handover proposed to {}", new_authority);
+
+    Ok(())
+}
\ No newline at end of file
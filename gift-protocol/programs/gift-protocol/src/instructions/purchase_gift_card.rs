@@ -0,0 +1,164 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct PurchaseGiftCard<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    /// CHECK: The listing's seller, who receives the proceeds and the listing rent
+    #[account(
+        mut,
+        address = listing.seller @ GiftProtocolError::StaleListing
+    )]
+    pub seller: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
+        bump = gift_card.bump,
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed,
+        constraint = gift_card.nft_mint.is_none() @ GiftProtocolError::AlreadyTokenized,
+        constraint = gift_card.recipient == listing.seller @ GiftProtocolError::StaleListing,
+        constraint = gift_card.amount == listing.card_amount @ GiftProtocolError::StaleListing
+    )]
+    pub gift_card: Account<'info, GiftCard>,
+
+    #[account(
+        mut,
+        close = seller,
+        seeds = [b"listing".as_ref(), gift_card.key().as_ref()],
+        bump = listing.bump,
+    )]
+    pub listing: Account<'info, Listing>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury".as_ref()],
+        bump = treasury.bump,
+        constraint = treasury.key() == config.treasury @ GiftProtocolError::TreasuryMismatch
+    )]
+    pub treasury: Account<'info, Treasury>,
+
+    /// Buyer's token account, required for SPL-priced listings
+    #[account(mut)]
+    pub buyer_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Seller's token account, required for SPL-priced listings
+    #[account(mut)]
+    pub seller_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Treasury-owned token account for the fee, required for SPL-priced listings
+    #[account(mut)]
+    pub treasury_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(
+    ctx: Context<PurchaseGiftCard>,
+    expected_price: u64,
+    expected_mint: Option<Pubkey>,
+) -> Result<()> {
+    // A card that expired while listed can no longer be sold
+    let current_time = ctx.accounts.clock.unix_timestamp;
+    let expiry_time = ctx.accounts.gift_card.expiry_time;
+    if expiry_time > 0 && current_time > expiry_time {
+        return Err(GiftProtocolError::Expired.into());
+    }
+
+    // The listing must still carry the terms the buyer agreed to; a seller can relist at a new price or mint
+    let price = ctx.accounts.listing.price;
+    let payment_mint = ctx.accounts.listing.payment_mint;
+    require!(price == expected_price, GiftProtocolError::StaleListing);
+    require!(payment_mint == expected_mint, GiftProtocolError::StaleListing);
+
+    // Marketplace fee (marketplace_fee_bps is in basis points, 10000 = 100%)
+    let fee = (price as u128)
+        .checked_mul(ctx.accounts.config.marketplace_fee_bps as u128)
+        .and_then(|fee| fee.checked_div(10000))
+        .ok_or(ProgramError::ArithmeticOverflow)? as u64;
+    let seller_amount = price.checked_sub(fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+
+    match payment_mint {
+        Some(mint) => {
+            let buyer_token_account = ctx.accounts.buyer_token_account.as_ref().ok_or(GiftProtocolError::InvalidPaymentAccount)?;
+            let seller_token_account = ctx.accounts.seller_token_account.as_ref().ok_or(GiftProtocolError::InvalidPaymentAccount)?;
+            let treasury_token_account = ctx.accounts.treasury_token_account.as_ref().ok_or(GiftProtocolError::InvalidPaymentAccount)?;
+            let token_program = ctx.accounts.token_program.as_ref().ok_or(GiftProtocolError::InvalidPaymentAccount)?;
+            require_keys_eq!(seller_token_account.mint, mint, GiftProtocolError::InvalidPaymentAccount);
+            require_keys_eq!(seller_token_account.owner, ctx.accounts.seller.key(), GiftProtocolError::InvalidPaymentAccount);
+            require_keys_eq!(treasury_token_account.mint, mint, GiftProtocolError::InvalidPaymentAccount);
+            require_keys_eq!(treasury_token_account.owner, ctx.accounts.treasury.key(), GiftProtocolError::InvalidPaymentAccount);
+
+            let cpi_accounts = Transfer {
+                from: buyer_token_account.to_account_info(),
+                to: seller_token_account.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token::transfer(cpi_ctx, seller_amount)?;
+
+            if fee > 0 {
+                let cpi_accounts = Transfer {
+                    from: buyer_token_account.to_account_info(),
+                    to: treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.buyer.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+                token::transfer(cpi_ctx, fee)?;
+            }
+        }
+        None => {
+            let cpi_accounts = system_program::Transfer {
+                from: ctx.accounts.buyer.to_account_info(),
+                to: ctx.accounts.seller.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            system_program::transfer(cpi_ctx, seller_amount)?;
+
+            if fee > 0 {
+                let cpi_accounts = system_program::Transfer {
+                    from: ctx.accounts.buyer.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                };
+                let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+                system_program::transfer(cpi_ctx, fee)?;
+
+                let treasury = &mut ctx.accounts.treasury;
+                treasury.balance = treasury.balance.checked_add(fee)
+                    .ok_or(ProgramError::ArithmeticOverflow)?;
+            }
+        }
+    }
+
+    // The buyer now holds the card
+    let gift_card = &mut ctx.accounts.gift_card;
+    gift_card.recipient = ctx.accounts.buyer.key();
+
+    emit!(GiftCardSold {
+        gift_card: gift_card.key(),
+        seller: ctx.accounts.seller.key(),
+        buyer: ctx.accounts.buyer.key(),
+        price,
+        payment_mint,
+        fee,
+    });
+
+    msg!("Gift card {} sold for {} ({} fee)", gift_card.key(), price, fee);
+
+    Ok(())
+}
\ No newline at end of file
@@ -1,23 +1,51 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount};
 use crate::state::*;
 use crate::errors::*;
+use crate::events::*;
 
 #[derive(Accounts)]
 pub struct RedeemGiftCard<'info> {
+    /// The card's recipient, or the holder of its NFT if the card was tokenized
     #[account(mut)]
     pub recipient: Signer<'info>,
 
     #[account(
         mut,
-        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &Pubkey::default().to_bytes()],
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
         bump = gift_card.bump,
-        constraint = gift_card.recipient == recipient.key() @ GiftProtocolError::NotAuthorized,
-        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed,
+        constraint = gift_card.merchants.is_empty() @ GiftProtocolError::ClosedLoopCard
     )]
     pub gift_card: Account<'info, GiftCard>,
 
+    /// The card's NFT mint, required for tokenized cards
     #[account(mut)]
+    pub nft_mint: Option<Account<'info, Mint>>,
+
+    /// The holder's NFT token account, required for tokenized cards
+    #[account(
+        mut,
+        token::authority = recipient,
+    )]
+    pub holder_token_account: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+
+    /// CHECK: Optional system account to receive the funds instead of the signer
+    #[account(
+        mut,
+        owner = system_program::ID @ GiftProtocolError::InvalidDestination,
+    )]
+    pub destination: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_REDEMPTION, clock.unix_timestamp) @ GiftProtocolError::RedemptionPaused,
+    )]
     pub config: Account<'info, Config>,
 
     pub system_program: Program<'info, System>,
@@ -33,6 +61,29 @@ pub fn handler(ctx: Context<RedeemGiftCard>) -> Result<()> {
         return Err(GiftProtocolError::Expired.into());
     }
 
+    // Tokenized cards are redeemed by burning the NFT; others by the named recipient
+    match gift_card.nft_mint {
+        Some(nft_mint_key) => {
+            let nft_mint = ctx.accounts.nft_mint.as_ref().ok_or(GiftProtocolError::NftRequired)?;
+            let holder_token_account = ctx.accounts.holder_token_account.as_ref().ok_or(GiftProtocolError::NftRequired)?;
+            let token_program = ctx.accounts.token_program.as_ref().ok_or(GiftProtocolError::NftRequired)?;
+            require_keys_eq!(nft_mint.key(), nft_mint_key, GiftProtocolError::InvalidNft);
+            require_keys_eq!(holder_token_account.mint, nft_mint_key, GiftProtocolError::InvalidNft);
+            require!(holder_token_account.amount == 1, GiftProtocolError::NftRequired);
+
+            let cpi_accounts = Burn {
+                mint: nft_mint.to_account_info(),
+                from: holder_token_account.to_account_info(),
+                authority: ctx.accounts.recipient.to_account_info(),
+            };
+            let cpi_ctx = CpiContext::new(token_program.to_account_info(), cpi_accounts);
+            token::burn(cpi_ctx, 1)?;
+        }
+        None => {
+            require_keys_eq!(gift_card.recipient, ctx.accounts.recipient.key(), GiftProtocolError::NotAuthorized);
+        }
+    }
+
     // Check if there are sufficient funds in the gift card account
     let gift_card_lamports = gift_card.to_account_info().lamports();
     require!(gift_card_lamports > 0, GiftProtocolError::InvalidAmount);
@@ -40,17 +91,26 @@ pub fn handler(ctx: Context<RedeemGiftCard>) -> Result<()> {
     // Mark the gift card as redeemed
     gift_card.is_redeemed = true;
 
-    // Calculate the amount to transfer to the recipient
-    // We need to keep some lamports in the gift card account to maintain rent exemption
-    let recipient_info = ctx.accounts.recipient.to_account_info();
+    // Funds go to the signer unless they named another destination
+    let destination_info = match ctx.accounts.destination.as_ref() {
+        Some(destination) => destination.to_account_info(),
+        None => ctx.accounts.recipient.to_account_info(),
+    };
 
-    // Transfer funds from gift card account to recipient
-    **recipient_info.lamports.borrow_mut() = recipient_info.lamports()
+    // Transfer funds from gift card account to the destination
+    **destination_info.lamports.borrow_mut() = destination_info.lamports()
         .checked_add(gift_card_lamports)
         .ok_or(ProgramError::ArithmeticOverflow)?;
     **gift_card.to_account_info().lamports.borrow_mut() = 0;
 
-    msg!("Gift card redeemed: {} lamports", gift_card_lamports);
+    emit!(GiftCardRedeemed {
+        gift_card: gift_card.key(),
+        redeemer: ctx.accounts.recipient.key(),
+        destination: destination_info.key(),
+        amount: gift_card_lamports,
+    });
+
+    msg!("Gift card redeemed: {} lamports to {}", gift_card_lamports, destination_info.key());
 
     Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,270 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+// Domain separator for relayed redemption requests
+pub const RELAYED_REDEEM_DOMAIN: &[u8] = b"gift-protocol:relayed-redeem:v1";
+
+// Layout of the ed25519 program's instruction data (one signature, all data inline)
+const ED25519_HEADER_LEN: usize = 2;
+const ED25519_OFFSETS_LEN: usize = 14;
+const ED25519_PUBKEY_LEN: usize = 32;
+const ED25519_SIGNATURE_LEN: usize = 64;
+
+#[derive(Accounts)]
+pub struct RedeemGiftCardRelayed<'info> {
+    /// Any fee payer submitting the recipient's signed request; receives the relayer fee
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
+        bump = gift_card.bump,
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed,
+        constraint = gift_card.nft_mint.is_none() @ GiftProtocolError::NftRequired,
+        constraint = gift_card.merchants.is_empty() @ GiftProtocolError::ClosedLoopCard
+    )]
+    pub gift_card: Account<'info, GiftCard>,
+
+    /// CHECK: System account receiving the card funds; bound by the recipient's signature
+    #[account(
+        mut,
+        owner = anchor_lang::system_program::ID @ GiftProtocolError::InvalidDestination,
+    )]
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_REDEMPTION, clock.unix_timestamp) @ GiftProtocolError::RedemptionPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Instructions sysvar, used to read the ed25519 signature check
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+// The message the recipient signs off-chain
+pub fn relayed_redeem_message(
+    gift_card: &Pubkey,
+    destination: &Pubkey,
+    max_relayer_fee: u64,
+    expiry: i64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(RELAYED_REDEEM_DOMAIN.len() + 32 + 32 + 8 + 8);
+    message.extend_from_slice(RELAYED_REDEEM_DOMAIN);
+    message.extend_from_slice(gift_card.as_ref());
+    message.extend_from_slice(destination.as_ref());
+    message.extend_from_slice(&max_relayer_fee.to_le_bytes());
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+// Check that the instruction just before this one is an ed25519 verification of
+// `message` by `signer`
+fn verify_ed25519_instruction(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, GiftProtocolError::InvalidRelaySignature);
+    let ix = load_instruction_at_checked((current_index - 1) as usize, instructions_sysvar)?;
+
+    require_keys_eq!(ix.program_id, ed25519_program::ID, GiftProtocolError::InvalidRelaySignature);
+    require!(ix.accounts.is_empty(), GiftProtocolError::InvalidRelaySignature);
+
+    verify_ed25519_data(&ix.data, signer, message)
+}
+
+// Check that ed25519 instruction data covers exactly one signature of `message`
+// by `signer`, with the key, signature and message all inline
+fn verify_ed25519_data(data: &[u8], signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require!(
+        data.len() >= ED25519_HEADER_LEN + ED25519_OFFSETS_LEN && data[0] == 1,
+        GiftProtocolError::InvalidRelaySignature
+    );
+
+    let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+    let offsets = ED25519_HEADER_LEN;
+    let signature_offset = read_u16(offsets) as usize;
+    let signature_ix_index = read_u16(offsets + 2);
+    let pubkey_offset = read_u16(offsets + 4) as usize;
+    let pubkey_ix_index = read_u16(offsets + 6);
+    let message_offset = read_u16(offsets + 8) as usize;
+    let message_size = read_u16(offsets + 10) as usize;
+    let message_ix_index = read_u16(offsets + 12);
+
+    // All referenced data must live in the ed25519 instruction itself
+    require!(
+        signature_ix_index == u16::MAX && pubkey_ix_index == u16::MAX && message_ix_index == u16::MAX,
+        GiftProtocolError::InvalidRelaySignature
+    );
+    require!(
+        signature_offset + ED25519_SIGNATURE_LEN <= data.len()
+            && pubkey_offset + ED25519_PUBKEY_LEN <= data.len()
+            && message_offset + message_size <= data.len(),
+        GiftProtocolError::InvalidRelaySignature
+    );
+
+    require!(
+        &data[pubkey_offset..pubkey_offset + ED25519_PUBKEY_LEN] == signer.as_ref(),
+        GiftProtocolError::InvalidRelaySignature
+    );
+    require!(
+        &data[message_offset..message_offset + message_size] == message,
+        GiftProtocolError::InvalidRelaySignature
+    );
+
+    Ok(())
+}
+
+pub fn handler(
+    ctx: Context<RedeemGiftCardRelayed>,
+    max_relayer_fee: u64,
+    expiry: i64,
+    relayer_fee: u64,
+) -> Result<()> {
+    let gift_card = &mut ctx.accounts.gift_card;
+    let current_time = ctx.accounts.clock.unix_timestamp;
+
+    // Validate the request
+    require!(current_time <= expiry, GiftProtocolError::RelayRequestExpired);
+    require!(relayer_fee <= max_relayer_fee, GiftProtocolError::RelayerFeeTooHigh);
+    if gift_card.expiry_time > 0 && current_time > gift_card.expiry_time {
+        return Err(GiftProtocolError::Expired.into());
+    }
+
+    // The recipient must have signed exactly this request
+    let destination = ctx.accounts.destination.key();
+    let message = relayed_redeem_message(&gift_card.key(), &destination, max_relayer_fee, expiry);
+    verify_ed25519_instruction(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &gift_card.recipient,
+        &message,
+    )?;
+
+    let gift_card_lamports = gift_card.to_account_info().lamports();
+    require!(gift_card_lamports > relayer_fee, GiftProtocolError::InvalidAmount);
+    let destination_amount = gift_card_lamports - relayer_fee;
+
+    // Mark the gift card as redeemed
+    gift_card.is_redeemed = true;
+
+    // Pay the relayer its fee and the destination the rest
+    let relayer_info = ctx.accounts.relayer.to_account_info();
+    let destination_info = ctx.accounts.destination.to_account_info();
+    **relayer_info.lamports.borrow_mut() = relayer_info.lamports()
+        .checked_add(relayer_fee)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **destination_info.lamports.borrow_mut() = destination_info.lamports()
+        .checked_add(destination_amount)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **gift_card.to_account_info().lamports.borrow_mut() = 0;
+
+    emit!(GiftCardRedeemed {
+        gift_card: gift_card.key(),
+        redeemer: gift_card.recipient,
+        destination,
+        amount: destination_amount,
+    });
+
+    msg!("Gift card redeemed via relayer: {} lamports to {}, {} lamports fee",
+        destination_amount, destination, relayer_fee);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lay out one inline signature the way the ed25519 program expects it
+    fn ed25519_data(signer: &Pubkey, message: &[u8]) -> Vec<u8> {
+        let pubkey_offset = ED25519_HEADER_LEN + ED25519_OFFSETS_LEN;
+        let signature_offset = pubkey_offset + ED25519_PUBKEY_LEN;
+        let message_offset = signature_offset + ED25519_SIGNATURE_LEN;
+
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset as u16,
+            u16::MAX,
+            pubkey_offset as u16,
+            u16::MAX,
+            message_offset as u16,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0; ED25519_SIGNATURE_LEN]);
+        data.extend_from_slice(message);
+        data
+    }
+
+    fn request() -> (Pubkey, Vec<u8>) {
+        let recipient = Pubkey::new_unique();
+        let message = relayed_redeem_message(&Pubkey::new_unique(), &Pubkey::new_unique(), 5_000, 1_700_000_000);
+        (recipient, message)
+    }
+
+    #[test]
+    fn accepts_matching_signature() {
+        let (recipient, message) = request();
+        assert!(verify_ed25519_data(&ed25519_data(&recipient, &message), &recipient, &message).is_ok());
+    }
+
+    #[test]
+    fn rejects_other_signer() {
+        let (recipient, message) = request();
+        let data = ed25519_data(&Pubkey::new_unique(), &message);
+        assert!(verify_ed25519_data(&data, &recipient, &message).is_err());
+    }
+
+    #[test]
+    fn rejects_other_message() {
+        let (recipient, message) = request();
+        let mut other = message.clone();
+        // Same request with a higher fee cap
+        let fee_cap = RELAYED_REDEEM_DOMAIN.len() + 64;
+        other[fee_cap] ^= 1;
+        assert!(verify_ed25519_data(&ed25519_data(&recipient, &other), &recipient, &message).is_err());
+    }
+
+    #[test]
+    fn rejects_data_in_other_instructions() {
+        let (recipient, message) = request();
+        // Signature, pubkey and message instruction indexes
+        for index_offset in [4, 8, 14] {
+            let mut data = ed25519_data(&recipient, &message);
+            data[index_offset..index_offset + 2].copy_from_slice(&0u16.to_le_bytes());
+            assert!(verify_ed25519_data(&data, &recipient, &message).is_err());
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_data() {
+        let (recipient, message) = request();
+        let data = ed25519_data(&recipient, &message);
+
+        // More than one signature
+        let mut multiple = data.clone();
+        multiple[0] = 2;
+        assert!(verify_ed25519_data(&multiple, &recipient, &message).is_err());
+
+        // Message running past the end of the data
+        let truncated = &data[..data.len() - 1];
+        assert!(verify_ed25519_data(truncated, &recipient, &message).is_err());
+
+        // Too short to hold the offsets
+        assert!(verify_ed25519_data(&data[..ED25519_HEADER_LEN], &recipient, &message).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RedeemLegacyGiftCard<'info> {
+    #[account(mut)]
+    pub recipient: Signer<'info>,
+
+    /// CHECK: Card in the pre-card_id layout; owner, address and data are verified in the handler
+    #[account(
+        mut,
+        owner = crate::ID @ GiftProtocolError::InvalidInstruction,
+    )]
+    pub legacy_gift_card: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_REDEMPTION, clock.unix_timestamp) @ GiftProtocolError::RedemptionPaused,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RedeemLegacyGiftCard>) -> Result<()> {
+    let card_info = ctx.accounts.legacy_gift_card.to_account_info();
+
+    let mut gift_card = {
+        let data = card_info.try_borrow_data()?;
+        require!(data.len() > 8 && data[..8] == GiftCard::DISCRIMINATOR, GiftProtocolError::InvalidInstruction);
+        LegacyGiftCard::deserialize(&mut &data[8..])?
+    };
+
+    // The account must sit at the old single-card-per-creator address
+    let expected = Pubkey::create_program_address(
+        &[b"gift_card".as_ref(), gift_card.creator.as_ref(), &Pubkey::default().to_bytes(), &[gift_card.bump]],
+        ctx.program_id,
+    ).map_err(|_| GiftProtocolError::InvalidInstruction)?;
+    require_keys_eq!(card_info.key(), expected, GiftProtocolError::InvalidInstruction);
+
+    require_keys_eq!(gift_card.recipient, ctx.accounts.recipient.key(), GiftProtocolError::NotAuthorized);
+    require!(!gift_card.is_redeemed, GiftProtocolError::AlreadyRedeemed);
+    if gift_card.expiry_time > 0 && ctx.accounts.clock.unix_timestamp > gift_card.expiry_time {
+        return Err(GiftProtocolError::Expired.into());
+    }
+
+    let gift_card_lamports = card_info.lamports();
+    require!(gift_card_lamports > 0, GiftProtocolError::InvalidAmount);
+
+    // Mark the gift card as redeemed
+    gift_card.is_redeemed = true;
+    gift_card.serialize(&mut &mut card_info.try_borrow_mut_data()?[8..])?;
+
+    let recipient_info = ctx.accounts.recipient.to_account_info();
+    **recipient_info.lamports.borrow_mut() = recipient_info.lamports()
+        .checked_add(gift_card_lamports)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **card_info.lamports.borrow_mut() = 0;
+
+    emit!(GiftCardRedeemed {
+        gift_card: card_info.key(),
+        redeemer: recipient_info.key(),
+        destination: recipient_info.key(),
+        amount: gift_card_lamports,
+    });
+
+    msg!("Legacy gift card redeemed: {} lamports", gift_card_lamports);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct RegisterMerchant<'info> {
+    /// The config authority; DAO listings go through a proposal's AddMerchant action instead
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Wallet that will operate the merchant
+    pub merchant_owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = Merchant::LEN,
+        seeds = [b"merchant".as_ref(), merchant_owner.key().as_ref()],
+        bump
+    )]
+    pub merchant: Account<'info, Merchant>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RegisterMerchant>, name: String, settlement: Pubkey) -> Result<()> {
+    require!(!name.is_empty() && name.len() <= MAX_MERCHANT_NAME_LEN, GiftProtocolError::NameTooLong);
+
+    let merchant = &mut ctx.accounts.merchant;
+    merchant.owner = ctx.accounts.merchant_owner.key();
+    merchant.settlement = settlement;
+    merchant.name = name;
+    merchant.is_active = true;
+    merchant.approved_by_proposal = None;
+    merchant.total_received = 0;
+    merchant.total_fees = 0;
+    merchant.payment_count = 0;
+    merchant.registered_at = ctx.accounts.clock.unix_timestamp;
+    merchant.bump = *ctx.bumps.get("merchant").unwrap();
+
+    emit!(MerchantRegistered {
+        merchant: merchant.key(),
+        owner: merchant.owner,
+        settlement,
+        approved_by_proposal: None,
+    });
+
+    msg!("Merchant {} registered for {}", merchant.name, merchant.owner);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RegisterReferralCode<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [b"referral".as_ref(), owner.key().as_ref()],
+        bump = referral.bump,
+    )]
+    pub referral: Account<'info, Referral>,
+
+    // Seeded by the uppercased code so "alice2026" and "ALICE2026" collide
+    #[account(
+        init,
+        payer = owner,
+        space = ReferralCode::LEN,
+        seeds = [b"referral_code".as_ref(), code.to_ascii_uppercase().as_bytes()],
+        bump
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+
+    pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+    let code = normalize_referral_code(&code).ok_or(GiftProtocolError::InvalidReferralCode)?;
+
+    let referral_code = &mut ctx.accounts.referral_code;
+    referral_code.code = code;
+    referral_code.owner = ctx.accounts.owner.key();
+    referral_code.created_at = ctx.accounts.clock.unix_timestamp;
+    referral_code.bump = *ctx.bumps.get("referral_code").unwrap();
+
+    msg!("Referral code {} registered for {}", referral_code.code, referral_code.owner);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct ReleaseReferralCode<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [b"referral_code".as_ref(), referral_code.code.as_bytes()],
+        bump = referral_code.bump,
+        constraint = referral_code.owner == owner.key() @ GiftProtocolError::NotAuthorized
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+}
+
+pub fn handler(ctx: Context<ReleaseReferralCode>) -> Result<()> {
+    msg!("Referral code {} released", ctx.accounts.referral_code.code);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetBreakagePolicy<'info> {
+    /// The config authority; the DAO changes the policy through a SetBreakagePolicy proposal action
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SetBreakagePolicy>, policy: u8, split_bps: u16) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.set_breakage_policy(policy, split_bps)?;
+
+    msg!("Breakage policy set to {} ({}bp to treasury)", config.breakage_policy, config.breakage_split_bps);
+
+    Ok(())
+}
\ No newline at end of file
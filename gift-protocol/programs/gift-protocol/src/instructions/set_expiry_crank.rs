@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetExpiryCrank<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SetExpiryCrank>, grace_period: i64, bounty: u64) -> Result<()> {
+    require!(grace_period >= 0, GiftProtocolError::InvalidAmount);
+
+    let config = &mut ctx.accounts.config;
+    config.expiry_grace_period = grace_period;
+    config.crank_bounty = bounty;
+
+    msg!("Expiry crank set: {}s grace period, {} lamport bounty", grace_period, bounty);
+
+    Ok(())
+}
\ No newline at end of file
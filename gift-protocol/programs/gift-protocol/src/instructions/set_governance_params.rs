@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetGovernanceParams<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SetGovernanceParams>, quorum_bps: u64, approval_threshold_bps: u64) -> Result<()> {
+    require!(quorum_bps <= 10000, GiftProtocolError::InvalidCommissionRate);
+    require!(approval_threshold_bps < 10000, GiftProtocolError::InvalidCommissionRate);
+
+    let config = &mut ctx.accounts.config;
+    config.quorum_bps = quorum_bps;
+    config.approval_threshold_bps = approval_threshold_bps;
+
+    msg!("Governance params set: {}bp quorum, {}bp approval threshold", quorum_bps, approval_threshold_bps);
+
+    Ok(())
+}
\ No newline at end of file
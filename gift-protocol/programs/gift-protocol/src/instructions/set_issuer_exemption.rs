@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetIssuerExemption<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The issuer being exempted
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + std::mem::size_of::<CreatorStats>(),
+        seeds = [b"creator_stats".as_ref(), creator.key().as_ref()],
+        bump
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetIssuerExemption>, is_exempt: bool) -> Result<()> {
+    let creator_stats = &mut ctx.accounts.creator_stats;
+    if creator_stats.creator == Pubkey::default() {
+        creator_stats.creator = ctx.accounts.creator.key();
+        creator_stats.bump = *ctx.bumps.get("creator_stats").unwrap();
+    }
+    creator_stats.is_exempt = is_exempt;
+
+    msg!("Issuer {} exemption: {}", creator_stats.creator, is_exempt);
+
+    Ok(())
+}
\ No newline at end of file
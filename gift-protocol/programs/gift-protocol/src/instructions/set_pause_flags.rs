@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetPauseFlags<'info> {
+    #[account(
+        constraint = signer.key() == config.pauser || signer.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    pub clock: Sysvar<'info, Clock>,
+}
+
+pub fn handler(ctx: Context<SetPauseFlags>, flags: u8) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // The redemption pause window is bounded and can't be restarted back-to-back
+    config.set_pause_flags(flags, ctx.accounts.clock.unix_timestamp)?;
+
+    msg!("Pause flags set to {:#06b}", flags);
+
+    Ok(())
+}
\ No newline at end of file
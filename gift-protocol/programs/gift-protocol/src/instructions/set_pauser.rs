@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetPauser<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SetPauser>, pauser: Pubkey) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.pauser = pauser;
+
+    msg!("Pauser set to {}", pauser);
+
+    Ok(())
+}
\ No newline at end of file
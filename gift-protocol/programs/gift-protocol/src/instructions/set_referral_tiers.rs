@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetReferralTiers<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(ctx: Context<SetReferralTiers>, tiers: [u64; MAX_REFERRAL_TIERS]) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    // All levels together must fit within the commission
+    Config::validate_rates(config.commission_rate, &tiers)?;
+
+    config.referral_tiers = tiers;
+    config.referral_rate = tiers[0];
+
+    msg!("Referral tiers set: {:?}bp", tiers);
+
+    Ok(())
+}
\ No newline at end of file
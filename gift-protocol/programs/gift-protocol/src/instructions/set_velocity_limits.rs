@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct SetVelocityLimits<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(
+    ctx: Context<SetVelocityLimits>,
+    max_card_amount: u64,
+    max_daily_volume: u64,
+    max_daily_cards: u32,
+    max_monthly_volume: u64,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.max_card_amount = max_card_amount;
+    config.max_daily_volume = max_daily_volume;
+    config.max_daily_cards = max_daily_cards;
+    config.max_monthly_volume = max_monthly_volume;
+
+    msg!("Velocity limits set: {} per card, {} / {} cards daily, {} monthly",
+        max_card_amount, max_daily_volume, max_daily_cards, max_monthly_volume);
+
+    Ok(())
+}
\ No newline at end of file
@@ -14,7 +14,8 @@ pub struct StakeTreasuryFunds<'info> {
     #[account(
         mut,
         seeds = [b"config".as_ref()],
-        bump = config.bump
+        bump = config.bump,
+        constraint = !config.is_paused(PAUSE_STAKING, clock.unix_timestamp) @ GiftProtocolError::StakingPaused,
     )]
     pub config: Account<'info, Config>,
 
@@ -31,6 +32,7 @@ pub struct StakeTreasuryFunds<'info> {
     pub staking_pool: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
+    pub clock: Sysvar<'info, Clock>,
 }
 
 pub fn handler(ctx: Context<StakeTreasuryFunds>, amount: u64) -> Result<()> {
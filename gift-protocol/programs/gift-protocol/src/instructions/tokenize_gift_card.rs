@@ -0,0 +1,140 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::metadata::{self, CreateMetadataAccountsV3, Metadata};
+use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
+use anchor_spl::token::{self, Mint, MintTo, SetAuthority, Token, TokenAccount};
+use anchor_spl::token::spl_token::instruction::AuthorityType;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct TokenizeGiftCard<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == gift_card.creator || authority.key() == gift_card.recipient @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
+        bump = gift_card.bump,
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed,
+        constraint = gift_card.nft_mint.is_none() @ GiftProtocolError::AlreadyTokenized
+    )]
+    pub gift_card: Account<'info, GiftCard>,
+
+    /// CHECK: The card's listing PDA; a listed card must be delisted before it can be tokenized
+    #[account(
+        seeds = [b"listing".as_ref(), gift_card.key().as_ref()],
+        bump,
+        constraint = listing.data_is_empty() @ GiftProtocolError::CardListed
+    )]
+    pub listing: UncheckedAccount<'info>,
+
+    /// CHECK: Only receives the NFT; must be the card's recipient
+    #[account(address = gift_card.recipient @ GiftProtocolError::NotAuthorized)]
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The 1-of-1 mint, with the gift card PDA as mint authority
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"gift_card_mint".as_ref(), gift_card.key().as_ref()],
+        bump,
+        mint::decimals = 0,
+        mint::authority = gift_card,
+    )]
+    pub nft_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        associated_token::mint = nft_mint,
+        associated_token::authority = recipient,
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Validated and initialized by the token metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata".as_ref(), token_metadata_program.key().as_ref(), nft_mint.key().as_ref()],
+        bump,
+        seeds::program = token_metadata_program.key(),
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    pub token_metadata_program: Program<'info, Metadata>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn handler(ctx: Context<TokenizeGiftCard>, base_uri: String) -> Result<()> {
+    let gift_card = &ctx.accounts.gift_card;
+
+    // The metadata URI embeds the card PDA and theme so wallets can link back to the card
+    let uri = format!("{}/{}/{}", base_uri.trim_end_matches('/'), gift_card.key(), gift_card.theme_id);
+    require!(uri.len() <= 200, GiftProtocolError::UriTooLong);
+
+    let creator_key = gift_card.creator;
+    let card_id = gift_card.card_id.to_le_bytes();
+    let gift_card_signer_seeds = &[
+        b"gift_card".as_ref(),
+        creator_key.as_ref(),
+        card_id.as_ref(),
+        &[gift_card.bump],
+    ];
+    let gift_card_signer = &[&gift_card_signer_seeds[..]];
+
+    // Create the metadata wallets use to display the card
+    let cpi_accounts = CreateMetadataAccountsV3 {
+        metadata: ctx.accounts.metadata.to_account_info(),
+        mint: ctx.accounts.nft_mint.to_account_info(),
+        mint_authority: ctx.accounts.gift_card.to_account_info(),
+        payer: ctx.accounts.authority.to_account_info(),
+        update_authority: ctx.accounts.gift_card.to_account_info(),
+        system_program: ctx.accounts.system_program.to_account_info(),
+        rent: ctx.accounts.rent.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_metadata_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, gift_card_signer);
+    let data = DataV2 {
+        name: "Gift Card".to_string(),
+        symbol: "GIFT".to_string(),
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+    metadata::create_metadata_accounts_v3(cpi_ctx, data, false, true, None)?;
+
+    // Mint the single token to the recipient
+    let cpi_accounts = MintTo {
+        mint: ctx.accounts.nft_mint.to_account_info(),
+        to: ctx.accounts.recipient_token_account.to_account_info(),
+        authority: ctx.accounts.gift_card.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, gift_card_signer);
+    token::mint_to(cpi_ctx, 1)?;
+
+    // Drop the mint authority so the supply is fixed at one
+    let cpi_accounts = SetAuthority {
+        current_authority: ctx.accounts.gift_card.to_account_info(),
+        account_or_mint: ctx.accounts.nft_mint.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, gift_card_signer);
+    token::set_authority(cpi_ctx, AuthorityType::MintTokens, None)?;
+
+    // From now on, whoever burns the token redeems the card
+    let gift_card = &mut ctx.accounts.gift_card;
+    gift_card.nft_mint = Some(ctx.accounts.nft_mint.key());
+
+    msg!("Gift card tokenized as {}", ctx.accounts.nft_mint.key());
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct TransferReferralCode<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"referral_code".as_ref(), referral_code.code.as_bytes()],
+        bump = referral_code.bump,
+        constraint = referral_code.owner == owner.key() @ GiftProtocolError::NotAuthorized
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+
+    /// The new owner's referral, so the code always resolves to a payable referrer
+    #[account(
+        seeds = [b"referral".as_ref(), new_owner_referral.owner.as_ref()],
+        bump = new_owner_referral.bump,
+    )]
+    pub new_owner_referral: Account<'info, Referral>,
+}
+
+pub fn handler(ctx: Context<TransferReferralCode>) -> Result<()> {
+    let referral_code = &mut ctx.accounts.referral_code;
+    referral_code.owner = ctx.accounts.new_owner_referral.owner;
+
+    msg!("Referral code {} transferred to {}", referral_code.code, referral_code.owner);
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+#[instruction(metadata_uri: Option<String>)]
+pub struct UpdateCardMetadata<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    // Resize the card by the difference between the old and new URI lengths
+    #[account(
+        mut,
+        seeds = [b"gift_card".as_ref(), gift_card.creator.as_ref(), &gift_card.card_id.to_le_bytes()],
+        bump = gift_card.bump,
+        has_one = creator @ GiftProtocolError::NotAuthorized,
+        constraint = !gift_card.is_redeemed @ GiftProtocolError::AlreadyRedeemed,
+        realloc = gift_card.to_account_info().data_len()
+            + metadata_uri.as_ref().map_or(0, |uri| uri.len())
+            - gift_card.metadata_uri.as_ref().map_or(0, |uri| uri.len()),
+        realloc::payer = creator,
+        realloc::zero = false,
+    )]
+    pub gift_card: Account<'info, GiftCard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateCardMetadata>,
+    metadata_uri: Option<String>,
+    content_hash: Option<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        metadata_uri.as_ref().is_none_or(|uri| uri.len() <= MAX_METADATA_URI_LEN),
+        GiftProtocolError::UriTooLong
+    );
+
+    let gift_card = &mut ctx.accounts.gift_card;
+    gift_card.metadata_uri = metadata_uri;
+    gift_card.content_hash = content_hash;
+
+    msg!("Gift card metadata updated");
+
+    Ok(())
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateConfig>,
+    commission_rate: u64,
+    referral_rate: u64,
+    settlement_fee_bps: u64,
+    marketplace_fee_bps: u64,
+) -> Result<()> {
+    // The direct referral rate plus the upline tiers must fit within the commission
+    let config = &mut ctx.accounts.config;
+    let mut tiers = config.referral_tiers;
+    tiers[0] = referral_rate;
+    Config::validate_rates(commission_rate, &tiers)?;
+
+    require!(settlement_fee_bps <= 10000, GiftProtocolError::InvalidCommissionRate);
+    require!(marketplace_fee_bps <= 10000, GiftProtocolError::InvalidCommissionRate);
+
+    emit!(ConfigUpdated {
+        old_commission_rate: config.commission_rate,
+        new_commission_rate: commission_rate,
+        old_referral_rate: config.referral_rate,
+        new_referral_rate: referral_rate,
+        old_settlement_fee_bps: config.settlement_fee_bps,
+        new_settlement_fee_bps: settlement_fee_bps,
+        old_marketplace_fee_bps: config.marketplace_fee_bps,
+        new_marketplace_fee_bps: marketplace_fee_bps,
+    });
+
+    config.commission_rate = commission_rate;
+    config.referral_rate = referral_rate;
+    config.referral_tiers[0] = referral_rate;
+    config.settlement_fee_bps = settlement_fee_bps;
+    config.marketplace_fee_bps = marketplace_fee_bps;
+
+    msg!("Config updated: commission rate {}bp, referral rate {}bp", commission_rate, referral_rate);
+
+    Ok(())
+}
\ No newline at end of file
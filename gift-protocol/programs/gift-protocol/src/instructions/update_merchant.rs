@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateMerchant<'info> {
+    /// The config authority or the merchant owner
+    #[account(
+        constraint = signer.key() == config.authority || signer.key() == merchant.owner @ GiftProtocolError::NotAuthorized
+    )]
+    pub signer: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"merchant".as_ref(), merchant.owner.as_ref()],
+        bump = merchant.bump,
+    )]
+    pub merchant: Account<'info, Merchant>,
+}
+
+pub fn handler(ctx: Context<UpdateMerchant>, settlement: Pubkey, is_active: bool) -> Result<()> {
+    let merchant = &mut ctx.accounts.merchant;
+
+    // Only the authority can suspend or reinstate a merchant
+    if is_active != merchant.is_active {
+        require_keys_eq!(ctx.accounts.signer.key(), ctx.accounts.config.authority, GiftProtocolError::NotAuthorized);
+    }
+
+    merchant.settlement = settlement;
+    merchant.is_active = is_active;
+
+    msg!("Merchant {} updated (active: {})", merchant.name, is_active);
+
+    Ok(())
+}
\ No newline at end of file
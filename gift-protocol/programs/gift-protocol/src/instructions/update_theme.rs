@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct UpdateTheme<'info> {
+    #[account(
+        constraint = authority.key() == config.authority @ GiftProtocolError::NotAuthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config.bump,
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"theme_registry".as_ref()],
+        bump = theme_registry.bump,
+    )]
+    pub theme_registry: Account<'info, ThemeRegistry>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateTheme>,
+    theme_id: u8,
+    name: String,
+    artwork_uri: String,
+    is_active: bool,
+    premium_bps: u16,
+) -> Result<()> {
+    // Validate inputs
+    require!(!name.is_empty() && name.len() <= MAX_THEME_NAME_LEN, GiftProtocolError::NameTooLong);
+    require!(artwork_uri.len() <= MAX_METADATA_URI_LEN, GiftProtocolError::UriTooLong);
+    require!(premium_bps <= 10000, GiftProtocolError::InvalidCommissionRate);
+
+    let theme = ctx.accounts.theme_registry.themes
+        .get_mut(theme_id as usize)
+        .ok_or(GiftProtocolError::InvalidTheme)?;
+    theme.name = name;
+    theme.artwork_uri = artwork_uri;
+    theme.is_active = is_active;
+    theme.premium_bps = premium_bps;
+
+    msg!("Theme {} updated (active: {})", theme_id, is_active);
+
+    Ok(())
+}
\ No newline at end of file
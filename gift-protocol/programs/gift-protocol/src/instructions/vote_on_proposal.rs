@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, TokenAccount};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
 
@@ -12,14 +12,20 @@ pub struct VoteOnProposal<'info> {
     #[account(
         seeds = [b"config".as_ref()],
         bump = config.bump,
+        constraint = !config.is_paused(PAUSE_GOVERNANCE, clock.unix_timestamp) @ GiftProtocolError::GovernancePaused,
     )]
     pub config: Account<'info, Config>,
 
-    /// The token account of the voter
     #[account(
-        token::mint = config.governance_token_mint.unwrap(),
+        address = config.governance_token_mint.unwrap_or_default() @ GiftProtocolError::InvalidTokenAuthority,
+    )]
+    pub governance_mint: Account<'info, Mint>,
+
+    /// The token account of the voter; its whole balance is locked into the vote vault
+    #[account(
+        mut,
+        token::mint = governance_mint,
         token::authority = voter,
-        constraint = voter_token_account.amount > 0 @ GiftProtocolError::NotAuthorized,
     )]
     pub voter_token_account: Account<'info, TokenAccount>,
 
@@ -27,7 +33,7 @@ pub struct VoteOnProposal<'info> {
         mut,
         seeds = [b"proposal".as_ref(), &proposal.proposal_id.to_le_bytes()],
         bump = proposal.bump,
-        constraint = !proposal.is_finalized @ GiftProtocolError::ProposalActive,
+        constraint = proposal.state == ProposalState::Active @ GiftProtocolError::ProposalNotActive,
         constraint = proposal.voting_end_time > clock.unix_timestamp @ GiftProtocolError::ProposalVotingEnded,
         constraint = choice_index < proposal.choices.len() as u8 @ GiftProtocolError::InvalidChoiceIndex,
     )]
@@ -46,7 +52,20 @@ pub struct VoteOnProposal<'info> {
     )]
     pub vote_record: Account<'info, VoteRecord>,
 
+    /// Escrow holding the voter's tokens until the proposal is over
+    #[account(
+        init_if_needed,
+        payer = voter,
+        seeds = [b"vote_vault".as_ref(), vote_record.key().as_ref()],
+        bump,
+        token::mint = governance_mint,
+        token::authority = vote_record,
+    )]
+    pub vote_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
     pub clock: Sysvar<'info, Clock>,
 }
 
@@ -54,8 +73,22 @@ pub fn handler(
     ctx: Context<VoteOnProposal>,
     choice_index: u8,
 ) -> Result<()> {
-    // Get the voter's token balance for vote weight
-    let vote_weight = ctx.accounts.voter_token_account.amount;
+    // Lock the voter's balance so the same tokens can't vote again from another wallet
+    let locked_now = ctx.accounts.voter_token_account.amount;
+    if locked_now > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.voter_token_account.to_account_info(),
+            to: ctx.accounts.vote_vault.to_account_info(),
+            authority: ctx.accounts.voter.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
+        token::transfer(cpi_ctx, locked_now)?;
+    }
+
+    // Vote weight is everything held in escrow for this vote
+    let vote_weight = ctx.accounts.vote_vault.amount
+        .checked_add(locked_now)
+        .ok_or(ProgramError::ArithmeticOverflow)?;
     require!(vote_weight > 0, GiftProtocolError::InvalidAmount);
 
     // Check if the user already voted
@@ -84,23 +117,22 @@ pub fn handler(
         // User already voted, check they're not double voting
         require!(vote_record.voter == ctx.accounts.voter.key(), GiftProtocolError::NotAuthorized);
         
-        // If they're changing their vote, update the counts
-        if vote_record.choice != choice_index {
-            // Subtract weight from the old choice
-            proposal.vote_counts[vote_record.choice as usize] = proposal.vote_counts[vote_record.choice as usize]
-                .checked_sub(vote_record.weight)
-                .ok_or(ProgramError::ArithmeticOverflow)?;
-                
-            // Add weight to the new choice
-            proposal.vote_counts[choice_index as usize] = proposal.vote_counts[choice_index as usize]
-                .checked_add(vote_weight)
-                .ok_or(ProgramError::ArithmeticOverflow)?;
-                
-            // Update the vote record
-            vote_record.choice = choice_index;
-            vote_record.weight = vote_weight;
-            vote_record.timestamp = ctx.accounts.clock.unix_timestamp;
-        }
+        // Move the old weight off the previous choice and the (possibly topped-up) weight onto the new one
+        proposal.vote_counts[vote_record.choice as usize] = proposal.vote_counts[vote_record.choice as usize]
+            .checked_sub(vote_record.weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        proposal.vote_counts[choice_index as usize] = proposal.vote_counts[choice_index as usize]
+            .checked_add(vote_weight)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        proposal.total_votes = proposal.total_votes
+            .checked_sub(vote_record.weight)
+            .and_then(|total| total.checked_add(vote_weight))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        // Update the vote record
+        vote_record.choice = choice_index;
+        vote_record.weight = vote_weight;
+        vote_record.timestamp = ctx.accounts.clock.unix_timestamp;
     }
     
     msg!("Voted on proposal: {}. Choice: {}", proposal.proposal_id, choice_index);
@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+#[derive(Accounts)]
+pub struct WithdrawVoteTokens<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"proposal".as_ref(), &proposal.proposal_id.to_le_bytes()],
+        bump = proposal.bump,
+        constraint = !matches!(proposal.state, ProposalState::Draft | ProposalState::Active) @ GiftProtocolError::ProposalActive,
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        mut,
+        close = voter,
+        seeds = [
+            b"vote".as_ref(),
+            voter.key().as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump = vote_record.bump,
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"vote_vault".as_ref(), vote_record.key().as_ref()],
+        bump,
+    )]
+    pub vote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = vote_vault.mint,
+        token::authority = voter,
+    )]
+    pub voter_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<WithdrawVoteTokens>) -> Result<()> {
+    let amount = ctx.accounts.vote_vault.amount;
+
+    let voter_key = ctx.accounts.voter.key();
+    let proposal_key = ctx.accounts.proposal.key();
+    let vote_record_signer_seeds = &[
+        b"vote".as_ref(),
+        voter_key.as_ref(),
+        proposal_key.as_ref(),
+        &[ctx.accounts.vote_record.bump],
+    ];
+    let vote_record_signer = &[&vote_record_signer_seeds[..]];
+
+    // Return the locked tokens
+    if amount > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vote_vault.to_account_info(),
+            to: ctx.accounts.voter_token_account.to_account_info(),
+            authority: ctx.accounts.vote_record.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, vote_record_signer);
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    // Close the empty vault, refunding its rent
+    let cpi_accounts = CloseAccount {
+        account: ctx.accounts.vote_vault.to_account_info(),
+        destination: ctx.accounts.voter.to_account_info(),
+        authority: ctx.accounts.vote_record.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), cpi_accounts, vote_record_signer);
+    token::close_account(cpi_ctx)?;
+
+    msg!("Withdrew {} vote tokens from proposal {}", amount, ctx.accounts.proposal.proposal_id);
+
+    Ok(())
+}
\ No newline at end of file
@@ -4,11 +4,13 @@ use std::mem::size_of;
 
 // Import program modules
 mod errors;
+mod events;
 mod instructions;
 mod state;
 
 // Re-exports
 pub use errors::*;
+pub use events::*;
 pub use instructions::*;
 pub use state::*;
 
@@ -22,21 +24,51 @@ pub mod gift_protocol {
     pub fn initialize(
         ctx: Context<Initialize>, 
         commission_rate: u64, 
-        referral_rate: u64
+        referral_rate: u64,
+        settlement_fee_bps: u64,
+        marketplace_fee_bps: u64,
     ) -> Result<()> {
-        instructions::initialize::handler(ctx, commission_rate, referral_rate)
+        instructions::initialize::handler(ctx, commission_rate, referral_rate, settlement_fee_bps, marketplace_fee_bps)
+    }
+
+    // Update the protocol rates within their bounds (authority)
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        commission_rate: u64,
+        referral_rate: u64,
+        settlement_fee_bps: u64,
+        marketplace_fee_bps: u64,
+    ) -> Result<()> {
+        instructions::update_config::handler(ctx, commission_rate, referral_rate, settlement_fee_bps, marketplace_fee_bps)
+    }
+
+    // Rewrite a config account created before the upgrade into the current layout (authority)
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config::handler(ctx)
+    }
+
+    // Extend a referral account created before the upgrade with the fields added since
+    pub fn migrate_referral(ctx: Context<MigrateReferral>) -> Result<()> {
+        instructions::migrate_referral::handler(ctx)
+    }
+
+    // Propose a new authority; it takes over once it accepts
+    pub fn propose_authority(ctx: Context<ProposeAuthority>, new_authority: Pubkey) -> Result<()> {
+        instructions::propose_authority::handler(ctx, new_authority)
+    }
+
+    // Accept a pending authority handover
+    pub fn accept_authority(ctx: Context<AcceptAuthority>) -> Result<()> {
+        instructions::accept_authority::handler(ctx)
     }
 
     // Create a gift card with funds
-    pub fn create_gift_card(
-        ctx: Context<CreateGiftCard>,
-        amount: u64,
-        recipient: Pubkey,
-        expiry_time: i64,
-        message: String,
-        referrer: Option<Pubkey>,
+    pub fn create_gift_card<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateGiftCard<'info>>,
+        card_id: u64,
+        params: GiftCardParams,
     ) -> Result<()> {
-        instructions::create_gift_card::handler(ctx, amount, recipient, expiry_time, message, referrer)
+        instructions::create_gift_card::handler(ctx, card_id, params)
     }
 
     // Redeem a gift card
@@ -44,9 +76,134 @@ pub mod gift_protocol {
         instructions::redeem_gift_card::handler(ctx)
     }
 
+    // Redeem a card created under the original single-card-per-creator seeds
+    pub fn redeem_legacy_gift_card(ctx: Context<RedeemLegacyGiftCard>) -> Result<()> {
+        instructions::redeem_legacy_gift_card::handler(ctx)
+    }
+
+    // Redeem a gift card through a relayer using the recipient's off-chain signature
+    pub fn redeem_gift_card_relayed(
+        ctx: Context<RedeemGiftCardRelayed>,
+        max_relayer_fee: u64,
+        expiry: i64,
+        relayer_fee: u64,
+    ) -> Result<()> {
+        instructions::redeem_gift_card_relayed::handler(ctx, max_relayer_fee, expiry, relayer_fee)
+    }
+
+    // Mint a 1-of-1 NFT that carries the card's redemption right
+    pub fn tokenize_gift_card(
+        ctx: Context<TokenizeGiftCard>,
+        base_uri: String,
+    ) -> Result<()> {
+        instructions::tokenize_gift_card::handler(ctx, base_uri)
+    }
+
+    // Append a chunk of the card's encrypted message
+    pub fn append_encrypted_message(
+        ctx: Context<AppendEncryptedMessage>,
+        scheme: u8,
+        ephemeral_pubkey: [u8; 32],
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        instructions::append_encrypted_message::handler(ctx, scheme, ephemeral_pubkey, chunk)
+    }
+
+    // Replace a card's off-chain metadata URI and content hash before redemption
+    pub fn update_card_metadata(
+        ctx: Context<UpdateCardMetadata>,
+        metadata_uri: Option<String>,
+        content_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::update_card_metadata::handler(ctx, metadata_uri, content_hash)
+    }
+
+    // List a merchant (authority; the DAO lists merchants through an AddMerchant proposal action)
+    pub fn register_merchant(
+        ctx: Context<RegisterMerchant>,
+        name: String,
+        settlement: Pubkey,
+    ) -> Result<()> {
+        instructions::register_merchant::handler(ctx, name, settlement)
+    }
+
+    // Change a merchant's settlement account or active status
+    pub fn update_merchant(
+        ctx: Context<UpdateMerchant>,
+        settlement: Pubkey,
+        is_active: bool,
+    ) -> Result<()> {
+        instructions::update_merchant::handler(ctx, settlement, is_active)
+    }
+
+    // Spend part or all of a card's balance at a merchant
+    pub fn pay_merchant(ctx: Context<PayMerchant>, amount: u64) -> Result<()> {
+        instructions::pay_merchant::handler(ctx, amount)
+    }
+
+    // List an unredeemed card for sale in SOL or an SPL mint
+    pub fn list_gift_card(
+        ctx: Context<ListGiftCard>,
+        price: u64,
+        payment_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::list_gift_card::handler(ctx, price, payment_mint)
+    }
+
+    // Buy a listed card and become its recipient
+    pub fn purchase_gift_card(
+        ctx: Context<PurchaseGiftCard>,
+        expected_price: u64,
+        expected_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::purchase_gift_card::handler(ctx, expected_price, expected_mint)
+    }
+
+    // Withdraw a card listing
+    pub fn delist_gift_card(ctx: Context<DelistGiftCard>) -> Result<()> {
+        instructions::delist_gift_card::handler(ctx)
+    }
+
     // Create a referral code
-    pub fn create_referral(ctx: Context<CreateReferral>) -> Result<()> {
-        instructions::create_referral::handler(ctx)
+    pub fn create_referral(ctx: Context<CreateReferral>, referred_by: Option<Pubkey>) -> Result<()> {
+        instructions::create_referral::handler(ctx, referred_by)
+    }
+
+    // Claim a vanity referral code for the caller's referral
+    pub fn register_referral_code(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+        instructions::register_referral_code::handler(ctx, code)
+    }
+
+    // Hand a vanity referral code to another referrer
+    pub fn transfer_referral_code(ctx: Context<TransferReferralCode>) -> Result<()> {
+        instructions::transfer_referral_code::handler(ctx)
+    }
+
+    // Give up a vanity referral code so it can be registered again
+    pub fn release_referral_code(ctx: Context<ReleaseReferralCode>) -> Result<()> {
+        instructions::release_referral_code::handler(ctx)
+    }
+
+    // Create a time-boxed referral or discount promotion (authority)
+    pub fn create_promo_campaign(
+        ctx: Context<CreatePromoCampaign>,
+        promo_id: u64,
+        params: PromoParams,
+    ) -> Result<()> {
+        instructions::create_promo_campaign::handler(ctx, promo_id, params)
+    }
+
+    // Set the referral rates per level (authority)
+    pub fn set_referral_tiers(
+        ctx: Context<SetReferralTiers>,
+        tiers: [u64; MAX_REFERRAL_TIERS],
+    ) -> Result<()> {
+        instructions::set_referral_tiers::handler(ctx, tiers)
+    }
+
+    // Withdraw the referral earnings held in the caller's referral account
+    pub fn claim_referral_earnings(ctx: Context<ClaimReferralEarnings>) -> Result<()> {
+        instructions::claim_referral_earnings::handler(ctx)
     }
 
     // Generate a DAO governance token
@@ -66,8 +223,28 @@ pub mod gift_protocol {
         description: String,
         choices: Vec<String>,
         voting_end_time: i64,
+        actions: Vec<Vec<ProposalAction>>,
     ) -> Result<()> {
-        instructions::create_proposal::handler(ctx, title, description, choices, voting_end_time)
+        instructions::create_proposal::handler(ctx, title, description, choices, voting_end_time, actions)
+    }
+
+    // Open a draft proposal for voting (creator)
+    pub fn activate_proposal(ctx: Context<ActivateProposal>) -> Result<()> {
+        instructions::activate_proposal::handler(ctx)
+    }
+
+    // Withdraw a draft, or an active proposal nobody has voted on (creator)
+    pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
+        instructions::cancel_proposal::handler(ctx)
+    }
+
+    // Set the governance quorum and approval threshold (authority)
+    pub fn set_governance_params(
+        ctx: Context<SetGovernanceParams>,
+        quorum_bps: u64,
+        approval_threshold_bps: u64,
+    ) -> Result<()> {
+        instructions::set_governance_params::handler(ctx, quorum_bps, approval_threshold_bps)
     }
 
     // Vote on a proposal
@@ -78,11 +255,21 @@ pub mod gift_protocol {
         instructions::vote_on_proposal::handler(ctx, choice_index)
     }
 
+    // Return tokens locked for a vote once the proposal is over
+    pub fn withdraw_vote_tokens(ctx: Context<WithdrawVoteTokens>) -> Result<()> {
+        instructions::withdraw_vote_tokens::handler(ctx)
+    }
+
     // Finalize a proposal after voting period ends
     pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
         instructions::finalize_proposal::handler(ctx)
     }
 
+    // Carry out the winning choice's actions once the timelock has passed
+    pub fn execute_proposal<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
+        instructions::execute_proposal::handler(ctx)
+    }
+
     // Admin function to stake treasury funds into a pool
     pub fn stake_treasury_funds(
         ctx: Context<StakeTreasuryFunds>,
@@ -90,4 +277,107 @@ pub mod gift_protocol {
     ) -> Result<()> {
         instructions::stake_treasury_funds::handler(ctx, amount)
     }
+
+    // Flip the emergency pause bits (pauser or authority)
+    pub fn set_pause_flags(ctx: Context<SetPauseFlags>, flags: u8) -> Result<()> {
+        instructions::set_pause_flags::handler(ctx, flags)
+    }
+
+    // Designate the pauser role
+    pub fn set_pauser(ctx: Context<SetPauser>, pauser: Pubkey) -> Result<()> {
+        instructions::set_pauser::handler(ctx, pauser)
+    }
+
+    // Set the expiry crank's grace period and bounty
+    pub fn set_expiry_crank(
+        ctx: Context<SetExpiryCrank>,
+        grace_period: i64,
+        bounty: u64,
+    ) -> Result<()> {
+        instructions::set_expiry_crank::handler(ctx, grace_period, bounty)
+    }
+
+    // Close an expired, unredeemed card under the breakage policy, paying the caller a bounty
+    pub fn crank_expired(ctx: Context<CrankExpired>) -> Result<()> {
+        instructions::crank_expired::handler(ctx)
+    }
+
+    // Set the breakage policy (authority; the DAO uses a SetBreakagePolicy proposal action)
+    pub fn set_breakage_policy(
+        ctx: Context<SetBreakagePolicy>,
+        policy: u8,
+        split_bps: u16,
+    ) -> Result<()> {
+        instructions::set_breakage_policy::handler(ctx, policy, split_bps)
+    }
+
+    // Set the per-creator issuance limits (0 disables a limit)
+    pub fn set_velocity_limits(
+        ctx: Context<SetVelocityLimits>,
+        max_card_amount: u64,
+        max_daily_volume: u64,
+        max_daily_cards: u32,
+        max_monthly_volume: u64,
+    ) -> Result<()> {
+        instructions::set_velocity_limits::handler(ctx, max_card_amount, max_daily_volume, max_daily_cards, max_monthly_volume)
+    }
+
+    // Exempt a verified issuer from the velocity limits, or revoke the exemption
+    pub fn set_issuer_exemption(ctx: Context<SetIssuerExemption>, is_exempt: bool) -> Result<()> {
+        instructions::set_issuer_exemption::handler(ctx, is_exempt)
+    }
+
+    // Fund a Merkle-distributor gift campaign
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        campaign_id: u64,
+        merkle_root: [u8; 32],
+        num_leaves: u32,
+        total_amount: u64,
+        claim_deadline: i64,
+    ) -> Result<()> {
+        instructions::create_campaign::handler(ctx, campaign_id, merkle_root, num_leaves, total_amount, claim_deadline)
+    }
+
+    // Claim a campaign leaf with a Merkle proof
+    pub fn claim_campaign(
+        ctx: Context<ClaimCampaign>,
+        index: u32,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::claim_campaign::handler(ctx, index, amount, proof)
+    }
+
+    // Return unclaimed campaign funds to the creator after the deadline
+    pub fn close_campaign(ctx: Context<CloseCampaign>) -> Result<()> {
+        instructions::close_campaign::handler(ctx)
+    }
+
+    // Create the theme registry
+    pub fn initialize_theme_registry(ctx: Context<InitializeThemeRegistry>) -> Result<()> {
+        instructions::initialize_theme_registry::handler(ctx)
+    }
+
+    // Register a new card theme
+    pub fn add_theme(
+        ctx: Context<AddTheme>,
+        name: String,
+        artwork_uri: String,
+        premium_bps: u16,
+    ) -> Result<()> {
+        instructions::add_theme::handler(ctx, name, artwork_uri, premium_bps)
+    }
+
+    // Update or retire an existing card theme
+    pub fn update_theme(
+        ctx: Context<UpdateTheme>,
+        theme_id: u8,
+        name: String,
+        artwork_uri: String,
+        is_active: bool,
+        premium_bps: u16,
+    ) -> Result<()> {
+        instructions::update_theme::handler(ctx, theme_id, name, artwork_uri, is_active, premium_bps)
+    }
 }
\ No newline at end of file
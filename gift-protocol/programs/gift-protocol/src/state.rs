@@ -1,17 +1,97 @@
 use anchor_lang::prelude::*;
 use std::collections::BTreeMap;
+use crate::errors::GiftProtocolError;
+
+// Pause bits stored in Config.pause_flags
+pub const PAUSE_CREATION: u8 = 1 << 0;
+pub const PAUSE_REDEMPTION: u8 = 1 << 1;
+pub const PAUSE_STAKING: u8 = 1 << 2;
+pub const PAUSE_GOVERNANCE: u8 = 1 << 3;
+
+// Longest a redemption pause stays in force (7 days), so recipients can't be locked out
+pub const MAX_REDEMPTION_PAUSE: i64 = 7 * 24 * 60 * 60;
+
+// Time after a redemption pause window ends before a new one can start
+pub const REDEMPTION_PAUSE_COOLDOWN: i64 = 7 * 24 * 60 * 60;
+
+// Defaults for the expiry crank, adjustable by the authority
+pub const DEFAULT_EXPIRY_GRACE_PERIOD: i64 = 30 * 24 * 60 * 60;
+pub const DEFAULT_CRANK_BOUNTY: u64 = 100_000;
+
+// Breakage policies stored in Config.breakage_policy, applied to expired cards
+pub const BREAKAGE_REFUND: u8 = 0;
+pub const BREAKAGE_SWEEP: u8 = 1;
+pub const BREAKAGE_SPLIT: u8 = 2;
+
+// Issuance velocity windows for CreatorStats: a rolling 24 hours in hourly buckets
+// and a rolling 30 days in daily buckets
+pub const VELOCITY_HOUR: i64 = 60 * 60;
+pub const VELOCITY_DAY: i64 = 24 * VELOCITY_HOUR;
+pub const VELOCITY_DAY_BUCKETS: usize = 24;
+pub const VELOCITY_MONTH_BUCKETS: usize = 30;
+
+// Referral levels paid per card: the direct referrer plus their upline
+pub const MAX_REFERRAL_TIERS: usize = 3;
+
+// Vanity referral code length bounds (A-Z and 0-9 only)
+pub const MIN_REFERRAL_CODE_LEN: usize = 3;
+pub const MAX_REFERRAL_CODE_LEN: usize = 16;
+
+// Delay between a proposal's finalization and execution of its actions
+pub const PROPOSAL_TIMELOCK: i64 = 2 * 24 * 60 * 60;
+
+// Default governance thresholds, in basis points
+pub const DEFAULT_QUORUM_BPS: u64 = 1000;
+pub const DEFAULT_APPROVAL_THRESHOLD_BPS: u64 = 5000;
+
+// Most actions a single proposal choice can carry
+pub const MAX_PROPOSAL_ACTIONS: usize = 4;
+
+// Largest Merkle campaign, keeping the claimed bitmap within a single account
+pub const MAX_CAMPAIGN_LEAVES: u32 = 65_536;
+
+// Encryption scheme tags for GiftCard.encrypted_message (see tools/message-crypto)
+pub const MESSAGE_SCHEME_X25519_CHACHA20POLY1305: u8 = 1;
+
+// Largest encrypted message a card can hold
+pub const MAX_CIPHERTEXT_LEN: usize = 4096;
+
+// Longest off-chain metadata URI a card can reference
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+// Theme registry limits
+pub const MAX_THEMES: usize = 32;
+pub const MAX_THEME_NAME_LEN: usize = 32;
+
+// Merchant registry limits
+pub const MAX_MERCHANT_NAME_LEN: usize = 32;
+pub const MAX_CARD_MERCHANTS: usize = 8;
 
 // Global configuration for the protocol
 #[account]
+#[derive(Default)]
 pub struct Config {
     // Authority that can update protocol settings
     pub authority: Pubkey,
+
+    // Proposed new authority, which must accept before the handover takes effect
+    pub pending_authority: Option<Pubkey>,
     
     // Commission rate in basis points (10000 = 100%)
     pub commission_rate: u64,
     
     // Referral rate in basis points (must be <= commission_rate)
     pub referral_rate: u64,
+
+    // Rates per referral level in basis points, [0] being the direct referrer (mirrors referral_rate);
+    // their sum must stay within commission_rate
+    pub referral_tiers: [u64; MAX_REFERRAL_TIERS],
+
+    // Fee on merchant payments in basis points, paid to the treasury
+    pub settlement_fee_bps: u64,
+
+    // Fee on secondary-market card sales in basis points, paid to the treasury
+    pub marketplace_fee_bps: u64,
     
     // Protocol treasury PDA
     pub treasury: Pubkey,
@@ -19,8 +99,11 @@ pub struct Config {
     // Total amount of commission collected
     pub total_commission: u64,
     
-    // Total referral payouts
+    // Total referral payouts credited to referral vaults
     pub total_referral_payouts: u64,
+
+    // Total referral earnings claimed out of the vaults
+    pub total_referral_claimed: u64,
     
     // Total number of gift cards created
     pub total_gift_cards: u64,
@@ -31,15 +114,105 @@ pub struct Config {
     // Governance token mint
     pub governance_token_mint: Option<Pubkey>,
 
+    // Account allowed to flip the pause bits (alongside the authority)
+    pub pauser: Pubkey,
+
+    // Emergency pause bits (PAUSE_*)
+    pub pause_flags: u8,
+
+    // Timestamp the redemption pause bit was raised
+    pub redemption_paused_at: i64,
+
+    // Time after expiry_time before anyone can crank an expired card
+    pub expiry_grace_period: i64,
+
+    // Lamports paid to the cranker out of the card's reclaimed rent
+    pub crank_bounty: u64,
+
+    // What happens to an expired card's balance (BREAKAGE_*)
+    pub breakage_policy: u8,
+
+    // Treasury share in basis points under BREAKAGE_SPLIT
+    pub breakage_split_bps: u16,
+
+    // Total expired-card funds taken into the treasury
+    pub total_breakage: u64,
+
+    // Votes needed for a proposal to count, as basis points of governance token supply
+    pub quorum_bps: u64,
+
+    // Share of votes cast the winning choice must exceed, in basis points
+    pub approval_threshold_bps: u64,
+
+    // Issuance limits per creator, over a rolling 24 hours and 30 days; 0 disables a limit
+    pub max_card_amount: u64,
+    pub max_daily_volume: u64,
+    pub max_daily_cards: u32,
+    pub max_monthly_volume: u64,
+
     // Bump seed for PDA derivation
     pub bump: u8,
 }
 
+impl Config {
+    // Whether the given pause bit is currently in force
+    pub fn is_paused(&self, flag: u8, now: i64) -> bool {
+        if self.pause_flags & flag == 0 {
+            return false;
+        }
+        if flag == PAUSE_REDEMPTION {
+            return now < self.redemption_paused_at.saturating_add(MAX_REDEMPTION_PAUSE);
+        }
+        true
+    }
+
+    // Replace the pause bits. Re-raising redemption inside the current window resumes it rather than
+    // starting a fresh one, and a new window can't open until the cooldown after the last one ends
+    pub fn set_pause_flags(&mut self, flags: u8, now: i64) -> Result<()> {
+        if flags & PAUSE_REDEMPTION != 0 && self.pause_flags & PAUSE_REDEMPTION == 0 {
+            let window_end = self.redemption_paused_at.saturating_add(MAX_REDEMPTION_PAUSE);
+            if self.redemption_paused_at == 0 || now >= window_end.saturating_add(REDEMPTION_PAUSE_COOLDOWN) {
+                self.redemption_paused_at = now;
+            } else {
+                require!(now < window_end, GiftProtocolError::RedemptionPauseCooldown);
+            }
+        }
+        self.pause_flags = flags;
+        Ok(())
+    }
+
+    // Switch the breakage policy for cards issued from now on; refund and sweep imply a 0% or 100% treasury share
+    pub fn set_breakage_policy(&mut self, policy: u8, split_bps: u16) -> Result<()> {
+        let split_bps = match policy {
+            BREAKAGE_REFUND => 0,
+            BREAKAGE_SWEEP => 10000,
+            BREAKAGE_SPLIT if split_bps <= 10000 => split_bps,
+            _ => return Err(GiftProtocolError::InvalidBreakagePolicy.into()),
+        };
+        self.breakage_policy = policy;
+        self.breakage_split_bps = split_bps;
+        Ok(())
+    }
+
+    // Check a commission rate against the referral tiers paid out of it; every level together must fit within it
+    pub fn validate_rates(commission_rate: u64, tiers: &[u64; MAX_REFERRAL_TIERS]) -> Result<()> {
+        require!(commission_rate <= 10000, GiftProtocolError::InvalidCommissionRate);
+        let total = tiers.iter()
+            .try_fold(0u64, |sum, rate| sum.checked_add(*rate))
+            .ok_or(GiftProtocolError::InvalidReferralRate)?;
+        require!(total <= commission_rate, GiftProtocolError::InvalidReferralRate);
+        Ok(())
+    }
+}
+
 // Gift card data account
 #[account]
 pub struct GiftCard {
     // Creator of the gift card
     pub creator: Pubkey,
+
+    // Creator-chosen identifier used in the PDA seeds
+    pub card_id: u64,
     
     // Recipient wallet address
     pub recipient: Pubkey,
@@ -55,15 +228,443 @@ pub struct GiftCard {
     
     // Optional message
     pub message: String,
+
+    // Optional message encrypted to the recipient, written in chunks after creation
+    pub encrypted_message: Option<EncryptedMessage>,
     
     // Referrer if provided
     pub referrer: Option<Pubkey>,
+
+    // Card design theme
+    pub theme_id: u8,
+
+    // Off-chain artwork, video or letter for the card
+    pub metadata_uri: Option<String>,
+
+    // SHA-256 of the content at metadata_uri, for integrity checks
+    pub content_hash: Option<[u8; 32]>,
+
+    // 1-of-1 token carrying the redemption right, if the card was tokenized
+    pub nft_mint: Option<Pubkey>,
+
+    // Merchants the card may be spent at; empty for an open-loop card
+    pub merchants: Vec<Pubkey>,
     
     // Bump seed for PDA derivation
     pub bump: u8,
+
+    // Breakage policy and treasury split in force when the card was issued
+    pub breakage_policy: u8,
+    pub breakage_split_bps: u16,
+}
+
+impl GiftCard {
+    // Treasury's share of the card's balance once it expires, under the policy it was issued with
+    pub fn breakage_for(&self, amount: u64) -> u64 {
+        match self.breakage_policy {
+            BREAKAGE_SWEEP => amount,
+            BREAKAGE_SPLIT => ((amount as u128 * self.breakage_split_bps as u128) / 10000) as u64,
+            _ => 0,
+        }
+    }
+}
+
+// Per-creator issuance stats used to enforce velocity limits
+#[account]
+#[derive(Default)]
+pub struct CreatorStats {
+    // Creator these stats belong to
+    pub creator: Pubkey,
+
+    // Hour (unix time / VELOCITY_HOUR) of the newest hourly bucket
+    pub last_hour: i64,
+
+    // Amount issued per hour over the last 24 hours, indexed by hour % 24
+    pub hourly_volume: [u64; VELOCITY_DAY_BUCKETS],
+
+    // Cards issued per hour over the last 24 hours, indexed by hour % 24
+    pub hourly_cards: [u32; VELOCITY_DAY_BUCKETS],
+
+    // Day (unix time / VELOCITY_DAY) of the newest daily bucket
+    pub last_day: i64,
+
+    // Amount issued per day over the last 30 days, indexed by day % 30
+    pub daily_volume: [u64; VELOCITY_MONTH_BUCKETS],
+
+    // Lifetime amount issued
+    pub total_volume: u64,
+
+    // Lifetime cards issued
+    pub total_cards: u64,
+
+    // Verified issuer exempt from the velocity limits, granted by the authority
+    pub is_exempt: bool,
+
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+// Zero the ring buffer buckets that fell out of the window between the `last` and `current` bucket
+fn expire_buckets<T: Copy + Default>(buckets: &mut [T], last: i64, current: i64) {
+    let len = buckets.len() as i64;
+    if current.saturating_sub(last) >= len {
+        buckets.fill(T::default());
+    } else {
+        for bucket in last.saturating_add(1)..=current {
+            buckets[bucket.rem_euclid(len) as usize] = T::default();
+        }
+    }
+}
+
+impl CreatorStats {
+    // Amount issued over the rolling 24 hours
+    pub fn rolling_daily_volume(&self) -> u64 {
+        self.hourly_volume.iter().fold(0, |sum, volume| sum.saturating_add(*volume))
+    }
+
+    // Cards issued over the rolling 24 hours
+    pub fn rolling_daily_cards(&self) -> u32 {
+        self.hourly_cards.iter().fold(0, |sum, cards| sum.saturating_add(*cards))
+    }
+
+    // Amount issued over the rolling 30 days
+    pub fn rolling_monthly_volume(&self) -> u64 {
+        self.daily_volume.iter().fold(0, |sum, volume| sum.saturating_add(*volume))
+    }
+
+    // Drop buckets older than the windows, enforce the config limits and count a new card
+    pub fn record_issuance(&mut self, config: &Config, amount: u64, now: i64) -> Result<()> {
+        // A clock that steps backwards keeps adding to the newest bucket
+        let hour = (now / VELOCITY_HOUR).max(self.last_hour);
+        expire_buckets(&mut self.hourly_volume, self.last_hour, hour);
+        expire_buckets(&mut self.hourly_cards, self.last_hour, hour);
+        self.last_hour = hour;
+
+        let day = (now / VELOCITY_DAY).max(self.last_day);
+        expire_buckets(&mut self.daily_volume, self.last_day, day);
+        self.last_day = day;
+
+        let hour_bucket = &mut self.hourly_volume[hour as usize % VELOCITY_DAY_BUCKETS];
+        *hour_bucket = hour_bucket.checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let hour_cards = &mut self.hourly_cards[hour as usize % VELOCITY_DAY_BUCKETS];
+        *hour_cards = hour_cards.checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        let day_bucket = &mut self.daily_volume[day as usize % VELOCITY_MONTH_BUCKETS];
+        *day_bucket = day_bucket.checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.total_volume = self.total_volume.checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.total_cards = self.total_cards.checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        if !self.is_exempt {
+            require!(config.max_card_amount == 0 || amount <= config.max_card_amount, GiftProtocolError::CardAmountLimitExceeded);
+            require!(config.max_daily_volume == 0 || self.rolling_daily_volume() <= config.max_daily_volume, GiftProtocolError::VelocityLimitExceeded);
+            require!(config.max_daily_cards == 0 || self.rolling_daily_cards() <= config.max_daily_cards, GiftProtocolError::VelocityLimitExceeded);
+            require!(config.max_monthly_volume == 0 || self.rolling_monthly_volume() <= config.max_monthly_volume, GiftProtocolError::VelocityLimitExceeded);
+        }
+
+        Ok(())
+    }
+}
+
+// Gift card layout from before per-card seeds, derived from [b"gift_card", creator, Pubkey::default()]
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyGiftCard {
+    pub creator: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub is_redeemed: bool,
+    pub expiry_time: i64,
+    pub message: String,
+    pub referrer: Option<Pubkey>,
+    pub bump: u8,
+}
+
+// Config layout from before the protocol settings added in this upgrade; migrate_config rewrites it
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyConfig {
+    pub authority: Pubkey,
+    pub commission_rate: u64,
+    pub referral_rate: u64,
+    pub treasury: Pubkey,
+    pub total_commission: u64,
+    pub total_referral_payouts: u64,
+    pub total_gift_cards: u64,
+    pub total_staked: u64,
+    pub governance_token_mint: Option<Pubkey>,
+    pub bump: u8,
+}
+
+impl From<LegacyConfig> for Config {
+    // Keep the existing settings and totals; everything new starts at its initialize default
+    fn from(legacy: LegacyConfig) -> Self {
+        let mut referral_tiers = [0; MAX_REFERRAL_TIERS];
+        referral_tiers[0] = legacy.referral_rate;
+
+        Config {
+            authority: legacy.authority,
+            commission_rate: legacy.commission_rate,
+            referral_rate: legacy.referral_rate,
+            referral_tiers,
+            treasury: legacy.treasury,
+            total_commission: legacy.total_commission,
+            total_referral_payouts: legacy.total_referral_payouts,
+            total_gift_cards: legacy.total_gift_cards,
+            total_staked: legacy.total_staked,
+            governance_token_mint: legacy.governance_token_mint,
+            pauser: legacy.authority,
+            expiry_grace_period: DEFAULT_EXPIRY_GRACE_PERIOD,
+            crank_bounty: DEFAULT_CRANK_BOUNTY,
+            breakage_policy: BREAKAGE_REFUND,
+            quorum_bps: DEFAULT_QUORUM_BPS,
+            approval_threshold_bps: DEFAULT_APPROVAL_THRESHOLD_BPS,
+            bump: legacy.bump,
+            ..Default::default()
+        }
+    }
+}
+
+// Registry of card themes, managed by the Config authority
+#[account]
+pub struct ThemeRegistry {
+    // Themes indexed by theme_id
+    pub themes: Vec<Theme>,
+
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ThemeRegistry {
+    // Account size with room for MAX_THEMES themes
+    pub const LEN: usize = 8 + 4 + MAX_THEMES * Theme::MAX_LEN + 1;
+}
+
+// A single card theme
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Theme {
+    // Display name
+    pub name: String,
+
+    // Artwork URI
+    pub artwork_uri: String,
+
+    // Whether new cards can use this theme
+    pub is_active: bool,
+
+    // Surcharge in basis points of the card amount, paid to the treasury
+    pub premium_bps: u16,
+}
+
+impl Theme {
+    pub const MAX_LEN: usize = (4 + MAX_THEME_NAME_LEN) + (4 + MAX_METADATA_URI_LEN) + 1 + 2;
+}
+
+// Message encrypted to the recipient's wallet key converted to x25519
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EncryptedMessage {
+    // Encryption scheme tag (MESSAGE_SCHEME_*)
+    pub scheme: u8,
+
+    // Sender's ephemeral x25519 public key
+    pub ephemeral_pubkey: [u8; 32],
+
+    // Ciphertext including the authentication tag
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedMessage {
+    // Serialized size excluding the ciphertext bytes
+    pub const HEADER_LEN: usize = 1 + 32 + 4;
+}
+
+// Merkle-distributor gift campaign; the account itself holds the unclaimed funds
+#[account]
+pub struct Campaign {
+    // Creator who funded the campaign and receives unclaimed funds
+    pub creator: Pubkey,
+
+    // Creator-chosen identifier used in the PDA seeds
+    pub campaign_id: u64,
+
+    // Root of the Merkle tree of (index, recipient, amount) leaves
+    pub merkle_root: [u8; 32],
+
+    // Number of leaves in the tree
+    pub num_leaves: u32,
+
+    // Total amount funded in lamports
+    pub total_amount: u64,
+
+    // Amount claimed so far
+    pub claimed_amount: u64,
+
+    // Number of claims made so far
+    pub num_claimed: u32,
+
+    // Claims close at this timestamp; the creator can then reclaim the rest
+    pub claim_deadline: i64,
+
+    // One bit per leaf, set once that leaf is claimed
+    pub claimed_bitmap: Vec<u8>,
+
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Campaign {
+    pub fn is_claimed(&self, index: u32) -> bool {
+        self.claimed_bitmap[(index / 8) as usize] & (1 << (index % 8)) != 0
+    }
+
+    pub fn set_claimed(&mut self, index: u32) {
+        self.claimed_bitmap[(index / 8) as usize] |= 1 << (index % 8);
+    }
+}
+
+// Participating merchant that gift cards can be spent at
+#[account]
+pub struct Merchant {
+    // Wallet that operates the merchant
+    pub owner: Pubkey,
+
+    // System account that receives card payments
+    pub settlement: Pubkey,
+
+    // Display name
+    pub name: String,
+
+    // Whether cards can currently be spent here
+    pub is_active: bool,
+
+    // Proposal that approved the listing, if not registered by the authority
+    pub approved_by_proposal: Option<Pubkey>,
+
+    // Total paid from cards, before the settlement fee
+    pub total_received: u64,
+
+    // Total settlement fees sent to the treasury
+    pub total_fees: u64,
+
+    // Number of card payments received
+    pub payment_count: u64,
+
+    // Timestamp when registered
+    pub registered_at: i64,
+
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Merchant {
+    pub const LEN: usize = 8 + 32 + 32 + (4 + MAX_MERCHANT_NAME_LEN) + 1 + (1 + 32) + 8 + 8 + 8 + 8 + 1;
+}
+
+// Secondary-market listing of an unredeemed gift card
+#[account]
+pub struct Listing {
+    // Recipient selling the card
+    pub seller: Pubkey,
+
+    // The listed gift card
+    pub gift_card: Pubkey,
+
+    // Asking price, in lamports or base units of payment_mint
+    pub price: u64,
+
+    // SPL mint the price is quoted in, or None for SOL
+    pub payment_mint: Option<Pubkey>,
+
+    // Card balance when listed; the sale fails if it has since changed
+    pub card_amount: u64,
+
+    // Timestamp when listed
+    pub created_at: i64,
+
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+// Time-boxed promotion overriding the referral rate and/or discounting the commission
+#[account]
+pub struct PromoCampaign {
+    // Authority-chosen identifier used in the PDA seeds
+    pub promo_id: u64,
+
+    // Promotion runs from start_time until end_time
+    pub start_time: i64,
+    pub end_time: i64,
+
+    // Direct referral rate in basis points while the promotion runs
+    pub referral_rate_override: Option<u64>,
+
+    // Basis points taken off the commission rate while the promotion runs
+    pub commission_discount_bps: u64,
+
+    // Total promotion cost allowed (commission given up plus extra referral rewards)
+    pub budget: u64,
+
+    // Promotion cost consumed so far
+    pub spent: u64,
+
+    // Promotion cost allowed per card creator; 0 means no per-user cap
+    pub per_user_cap: u64,
+
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PromoCampaign {
+    pub fn is_active(&self, now: i64) -> bool {
+        now >= self.start_time && now < self.end_time
+    }
+
+    // Commission and direct referral rates under this promo, or None if the referral payouts
+    // no longer fit within the discounted commission at the config's current rates
+    pub fn rates(&self, config: &Config) -> Option<(u64, u64)> {
+        let commission_rate = config.commission_rate.saturating_sub(self.commission_discount_bps);
+        let referral_rate = self.referral_rate_override.unwrap_or(config.referral_rate);
+        let upline_rates: u64 = config.referral_tiers[1..].iter().sum();
+        if referral_rate.checked_add(upline_rates)? > commission_rate {
+            return None;
+        }
+        Some((commission_rate, referral_rate))
+    }
+
+    // Charge a promotion's cost to the campaign and user budgets, or false if either would be exceeded
+    pub fn try_spend(&mut self, usage: &mut PromoUsage, cost: u64) -> bool {
+        let spent = match self.spent.checked_add(cost) {
+            Some(spent) if spent <= self.budget => spent,
+            _ => return false,
+        };
+        let user_spent = match usage.spent.checked_add(cost) {
+            Some(user_spent) if self.per_user_cap == 0 || user_spent <= self.per_user_cap => user_spent,
+            _ => return false,
+        };
+        self.spent = spent;
+        usage.spent = user_spent;
+        true
+    }
+}
+
+// Per-creator spend against a promo campaign
+#[account]
+pub struct PromoUsage {
+    // The promo campaign
+    pub promo: Pubkey,
+
+    // The card creator
+    pub user: Pubkey,
+
+    // Promotion cost consumed by this creator
+    pub spent: u64,
+
+    // Bump seed for PDA derivation
+    pub bump: u8,
 }
 
-// User's referral data
+// User's referral data; the account also holds the owner's unclaimed earnings
 #[account]
 pub struct Referral {
     // The user who owns this referral code
@@ -80,6 +681,59 @@ pub struct Referral {
     
     // Bump seed for PDA derivation
     pub bump: u8,
+
+    // Fields below were added after launch and must stay at the end, so migrate_referral
+    // can extend existing accounts with zeroes
+
+    // Owner of the referral code that referred this owner, if any
+    pub referred_by: Option<Pubkey>,
+
+    // Earnings held in this account awaiting claim_referral_earnings
+    pub unclaimed_earnings: u64,
+
+    // Total earnings claimed by the owner
+    pub total_claimed: u64,
+}
+
+// Referral layout from before claimable earnings and referral tiers; migrate_referral extends it in place
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyReferral {
+    pub owner: Pubkey,
+    pub total_earned: u64,
+    pub referral_count: u64,
+    pub created_at: i64,
+    pub bump: u8,
+}
+
+// Vanity referral code pointing at a Referral owner, addressed by the normalized code
+#[account]
+pub struct ReferralCode {
+    // Normalized code (uppercase A-Z and 0-9)
+    pub code: String,
+
+    // Owner of the Referral the code resolves to
+    pub owner: Pubkey,
+
+    // Timestamp when registered
+    pub created_at: i64,
+
+    // Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ReferralCode {
+    pub const LEN: usize = 8 + (4 + MAX_REFERRAL_CODE_LEN) + 32 + 8 + 1;
+}
+
+// Normalize a vanity referral code to uppercase, or None if it breaks the length or character rules
+pub fn normalize_referral_code(code: &str) -> Option<String> {
+    if code.len() < MIN_REFERRAL_CODE_LEN || code.len() > MAX_REFERRAL_CODE_LEN {
+        return None;
+    }
+    if !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    Some(code.to_ascii_uppercase())
 }
 
 // Protocol Treasury account
@@ -119,10 +773,10 @@ pub struct Proposal {
     // End timestamp for voting period
     pub voting_end_time: i64,
     
-    // Whether the proposal has been finalized
-    pub is_finalized: bool,
+    // Lifecycle state
+    pub state: ProposalState,
     
-    // Index of the winning choice (if finalized)
+    // Index of the winning choice (if the proposal succeeded)
     pub winning_choice: Option<u8>,
     
     // Total votes cast
@@ -133,11 +787,108 @@ pub struct Proposal {
     
     // Proposal number/ID
     pub proposal_id: u64,
+
+    // Actions per choice, executed for the winning choice; empty for a text-only proposal
+    pub actions: Vec<Vec<ProposalAction>>,
+
+    // Timestamp of finalization, starting the execution timelock
+    pub finalized_at: i64,
     
     // Bump seed for PDA derivation
     pub bump: u8,
 }
 
+impl Proposal {
+    // Whether the proposal passed, whether or not its actions have run yet
+    pub fn has_passed(&self) -> bool {
+        matches!(self.state, ProposalState::Succeeded | ProposalState::Executed)
+    }
+
+    // Close voting: the proposal succeeds if enough of the governance supply voted and a
+    // single top choice clears the approval threshold; a tie for the top leaves no winner
+    pub fn finalize(&mut self, config: &Config, supply: u64, now: i64) {
+        let mut winning_choice: Option<u8> = None;
+        let mut max_votes: u64 = 0;
+
+        for (i, &votes) in self.vote_counts.iter().enumerate() {
+            if votes > max_votes {
+                max_votes = votes;
+                winning_choice = Some(i as u8);
+            } else if votes == max_votes {
+                winning_choice = None;
+            }
+        }
+
+        let total_votes = self.total_votes as u128;
+        let quorum_met = total_votes * 10000 >= supply as u128 * config.quorum_bps as u128;
+        let threshold_met = max_votes as u128 * 10000 > total_votes * config.approval_threshold_bps as u128;
+
+        let succeeded = max_votes > 0 && winning_choice.is_some() && quorum_met && threshold_met;
+        self.state = if succeeded { ProposalState::Succeeded } else { ProposalState::Defeated };
+        self.winning_choice = if succeeded { winning_choice } else { None };
+        self.finalized_at = now;
+    }
+}
+
+// Proposal lifecycle
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ProposalState {
+    // Created; the creator can still cancel it before voting opens
+    Draft,
+    // Open for voting until voting_end_time
+    Active,
+    // Met quorum and its winning choice cleared the approval threshold
+    Succeeded,
+    // Missed quorum or threshold, tied, or drew no votes
+    Defeated,
+    // Succeeded and the winning choice's actions have run
+    Executed,
+    // Withdrawn by its creator before any votes were cast
+    Cancelled,
+}
+
+// Typed action a proposal carries out when its choice wins
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
+pub enum ProposalAction {
+    // Pay amount lamports, or base units of mint, from the treasury to recipient
+    TreasurySpend {
+        recipient: Pubkey,
+        amount: u64,
+        mint: Option<Pubkey>,
+    },
+
+    // Change the commission and direct referral rates
+    SetRates {
+        commission_rate: u64,
+        referral_rate: u64,
+    },
+
+    // Replace the emergency pause bits
+    SetPauseFlags {
+        flags: u8,
+    },
+
+    // List a merchant in the registry
+    AddMerchant {
+        owner: Pubkey,
+        name: String,
+        settlement: Pubkey,
+    },
+
+    // Register a new card theme
+    AddTheme {
+        name: String,
+        artwork_uri: String,
+        premium_bps: u16,
+    },
+
+    // Change what happens to expired card balances (BREAKAGE_*)
+    SetBreakagePolicy {
+        policy: u8,
+        split_bps: u16,
+    },
+}
+
 // Vote record to track user votes
 #[account]
 pub struct VoteRecord {
@@ -150,7 +901,7 @@ pub struct VoteRecord {
     // The chosen option index
     pub choice: u8,
     
-    // Vote weight (based on token holdings)
+    // Vote weight (tokens locked in the vote vault)
     pub weight: u64,
     
     // Timestamp of the vote
@@ -158,4 +909,323 @@ pub struct VoteRecord {
     
     // Bump seed for PDA derivation
     pub bump: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn velocity_config() -> Config {
+        Config {
+            max_daily_volume: 1_000,
+            max_daily_cards: 3,
+            max_monthly_volume: 5_000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn daily_cap_holds_across_a_day_boundary() {
+        let config = velocity_config();
+        let mut stats = CreatorStats::default();
+        let day_start = 1_700_006_400;
+
+        // Full cap just before the day ends, then straight after: the second issuance is still inside the 24h window
+        stats.record_issuance(&config, 1_000, day_start + VELOCITY_DAY - 1).unwrap();
+        assert!(stats.record_issuance(&config, 1, day_start + VELOCITY_DAY + 1).is_err());
+    }
+
+    #[test]
+    fn daily_cap_frees_up_after_24_hours() {
+        let config = velocity_config();
+        let mut stats = CreatorStats::default();
+        let start = 1_700_000_000;
+
+        stats.record_issuance(&config, 1_000, start).unwrap();
+        assert!(stats.clone().record_issuance(&config, 1, start + VELOCITY_DAY - VELOCITY_HOUR).is_err());
+        stats.record_issuance(&config, 1_000, start + VELOCITY_DAY).unwrap();
+        assert_eq!(stats.rolling_daily_volume(), 1_000);
+    }
+
+    #[test]
+    fn daily_card_count_rolls() {
+        let config = velocity_config();
+        let mut stats = CreatorStats::default();
+        let start = 1_700_000_000;
+
+        for hour in 0..3 {
+            stats.record_issuance(&config, 1, start + hour * VELOCITY_HOUR).unwrap();
+        }
+        assert!(stats.clone().record_issuance(&config, 1, start + 3 * VELOCITY_HOUR).is_err());
+
+        // The first card drops out of the window one day after it was issued
+        stats.record_issuance(&config, 1, start + VELOCITY_DAY).unwrap();
+        assert_eq!(stats.rolling_daily_cards(), 3);
+    }
+
+    #[test]
+    fn monthly_cap_holds_across_a_month_boundary() {
+        let config = velocity_config();
+        let mut stats = CreatorStats::default();
+        let start = 1_700_006_400;
+
+        for day in 0..5 {
+            stats.record_issuance(&config, 1_000, start + day * VELOCITY_DAY).unwrap();
+        }
+        assert!(stats.clone().record_issuance(&config, 1_000, start + 29 * VELOCITY_DAY).is_err());
+
+        // 30 days after the first issuance it no longer counts
+        stats.record_issuance(&config, 1_000, start + 30 * VELOCITY_DAY).unwrap();
+        assert_eq!(stats.rolling_monthly_volume(), 5_000);
+    }
+
+    #[test]
+    fn exempt_issuers_skip_velocity_limits() {
+        let config = velocity_config();
+        let mut stats = CreatorStats { is_exempt: true, ..Default::default() };
+
+        stats.record_issuance(&config, 10_000, 1_700_000_000).unwrap();
+        assert_eq!(stats.total_volume, 10_000);
+    }
+
+    // A card issued under the config's current breakage policy
+    fn issued_card(config: &Config) -> GiftCard {
+        GiftCard {
+            creator: Pubkey::default(),
+            card_id: 1,
+            recipient: Pubkey::default(),
+            amount: 1_000,
+            is_redeemed: false,
+            expiry_time: 0,
+            message: String::new(),
+            encrypted_message: None,
+            referrer: None,
+            theme_id: 0,
+            metadata_uri: None,
+            content_hash: None,
+            nft_mint: None,
+            merchants: Vec::new(),
+            bump: 0,
+            breakage_policy: config.breakage_policy,
+            breakage_split_bps: config.breakage_split_bps,
+        }
+    }
+
+    #[test]
+    fn breakage_split_rounds_down_to_the_treasury() {
+        let mut config = Config::default();
+        config.set_breakage_policy(BREAKAGE_SPLIT, 2_500).unwrap();
+        let card = issued_card(&config);
+
+        assert_eq!(card.breakage_for(1_000), 250);
+        assert_eq!(card.breakage_for(1_003), 250);
+        assert_eq!(card.breakage_for(u64::MAX), u64::MAX / 4);
+    }
+
+    #[test]
+    fn breakage_refund_and_sweep_ignore_the_split() {
+        let mut config = Config::default();
+
+        config.set_breakage_policy(BREAKAGE_SWEEP, 2_500).unwrap();
+        assert_eq!(config.breakage_split_bps, 10000);
+        assert_eq!(issued_card(&config).breakage_for(1_000), 1_000);
+
+        config.set_breakage_policy(BREAKAGE_REFUND, 2_500).unwrap();
+        assert_eq!(config.breakage_split_bps, 0);
+        assert_eq!(issued_card(&config).breakage_for(1_000), 0);
+    }
+
+    #[test]
+    fn breakage_policy_change_spares_issued_cards() {
+        let mut config = Config::default();
+        config.set_breakage_policy(BREAKAGE_REFUND, 0).unwrap();
+        let card = issued_card(&config);
+
+        // Switching to sweep only affects cards issued afterwards
+        config.set_breakage_policy(BREAKAGE_SWEEP, 0).unwrap();
+        assert_eq!(card.breakage_for(1_000), 0);
+        assert_eq!(issued_card(&config).breakage_for(1_000), 1_000);
+    }
+
+    #[test]
+    fn breakage_policy_rejects_bad_input() {
+        let mut config = Config::default();
+        config.set_breakage_policy(BREAKAGE_SPLIT, 5_000).unwrap();
+
+        assert!(config.set_breakage_policy(BREAKAGE_SPLIT, 10_001).is_err());
+        assert!(config.set_breakage_policy(3, 0).is_err());
+
+        // A rejected change leaves the current policy alone
+        assert_eq!(config.breakage_policy, BREAKAGE_SPLIT);
+        assert_eq!(config.breakage_split_bps, 5_000);
+    }
+
+    #[test]
+    fn referral_codes_normalize_to_upper_case() {
+        assert_eq!(normalize_referral_code("gift2024").as_deref(), Some("GIFT2024"));
+        assert_eq!(normalize_referral_code("GiFt").as_deref(), Some("GIFT"));
+        assert_eq!(normalize_referral_code("abc").as_deref(), Some("ABC"));
+        assert_eq!(normalize_referral_code("a234567890123456").as_deref(), Some("A234567890123456"));
+    }
+
+    #[test]
+    fn referral_codes_reject_bad_input() {
+        // Too short or too long
+        assert_eq!(normalize_referral_code("ab"), None);
+        assert_eq!(normalize_referral_code("a2345678901234567"), None);
+
+        // Only ASCII letters and digits
+        assert_eq!(normalize_referral_code("gift-card"), None);
+        assert_eq!(normalize_referral_code("gift card"), None);
+        assert_eq!(normalize_referral_code("caf\u{e9}s"), None);
+        assert_eq!(normalize_referral_code(""), None);
+    }
+
+    fn governance_config() -> Config {
+        Config {
+            quorum_bps: 2_000,
+            approval_threshold_bps: 5_000,
+            ..Default::default()
+        }
+    }
+
+    fn voted_proposal(vote_counts: &[u64]) -> Proposal {
+        Proposal {
+            creator: Pubkey::default(),
+            title: String::new(),
+            description: String::new(),
+            choices: vote_counts.iter().map(|_| String::new()).collect(),
+            vote_counts: vote_counts.to_vec(),
+            voting_end_time: 0,
+            state: ProposalState::Active,
+            winning_choice: None,
+            total_votes: vote_counts.iter().sum(),
+            created_at: 0,
+            proposal_id: 1,
+            actions: Vec::new(),
+            finalized_at: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn proposal_with_quorum_and_majority_succeeds() {
+        let mut proposal = voted_proposal(&[150, 50]);
+        proposal.finalize(&governance_config(), 1_000, 1_700_000_000);
+
+        assert_eq!(proposal.state, ProposalState::Succeeded);
+        assert_eq!(proposal.winning_choice, Some(0));
+        assert_eq!(proposal.finalized_at, 1_700_000_000);
+        assert!(proposal.has_passed());
+    }
+
+    #[test]
+    fn proposal_below_quorum_is_defeated() {
+        // 199 of 1,000 supply is just under the 20% quorum
+        let mut proposal = voted_proposal(&[199, 0]);
+        proposal.finalize(&governance_config(), 1_000, 0);
+        assert_eq!(proposal.state, ProposalState::Defeated);
+        assert_eq!(proposal.winning_choice, None);
+
+        let mut proposal = voted_proposal(&[200, 0]);
+        proposal.finalize(&governance_config(), 1_000, 0);
+        assert_eq!(proposal.state, ProposalState::Succeeded);
+    }
+
+    #[test]
+    fn proposal_must_clear_the_threshold() {
+        // Exactly half is not above the 50% threshold
+        let mut proposal = voted_proposal(&[150, 100, 50]);
+        proposal.finalize(&governance_config(), 1_000, 0);
+        assert_eq!(proposal.state, ProposalState::Defeated);
+
+        let mut proposal = voted_proposal(&[151, 100, 50]);
+        proposal.finalize(&governance_config(), 1_000, 0);
+        assert_eq!(proposal.winning_choice, Some(0));
+    }
+
+    #[test]
+    fn tied_or_empty_proposal_is_defeated() {
+        let mut proposal = voted_proposal(&[300, 300]);
+        proposal.finalize(&governance_config(), 1_000, 0);
+        assert_eq!(proposal.state, ProposalState::Defeated);
+        assert_eq!(proposal.winning_choice, None);
+
+        // A later choice beating a tie still wins
+        let mut proposal = voted_proposal(&[100, 100, 400]);
+        proposal.finalize(&governance_config(), 1_000, 0);
+        assert_eq!(proposal.winning_choice, Some(2));
+
+        // No votes fails even with no quorum configured
+        let mut proposal = voted_proposal(&[0, 0]);
+        proposal.finalize(&Config::default(), 0, 0);
+        assert_eq!(proposal.state, ProposalState::Defeated);
+    }
+
+    #[test]
+    fn rates_reject_tiers_above_commission_or_overflowing() {
+        let mut tiers = [0u64; MAX_REFERRAL_TIERS];
+        tiers[0] = 300;
+        tiers[1] = 200;
+        assert!(Config::validate_rates(500, &tiers).is_ok());
+        assert!(Config::validate_rates(499, &tiers).is_err());
+        assert!(Config::validate_rates(10001, &[0; MAX_REFERRAL_TIERS]).is_err());
+
+        // A sum that wraps around must not slip under the commission
+        tiers[1] = u64::MAX;
+        assert!(Config::validate_rates(500, &tiers).is_err());
+    }
+
+    #[test]
+    fn legacy_config_migrates_with_defaults() {
+        let authority = Pubkey::new_unique();
+        let legacy = LegacyConfig {
+            authority,
+            commission_rate: 300,
+            referral_rate: 100,
+            treasury: Pubkey::new_unique(),
+            total_commission: 5_000,
+            total_referral_payouts: 1_000,
+            total_gift_cards: 7,
+            total_staked: 0,
+            governance_token_mint: None,
+            bump: 254,
+        };
+        let data = legacy.try_to_vec().unwrap();
+
+        let config = Config::from(LegacyConfig::deserialize(&mut &data[..]).unwrap());
+        assert_eq!(config.authority, authority);
+        assert_eq!(config.pauser, authority);
+        assert_eq!(config.referral_tiers, [100, 0, 0]);
+        assert_eq!(config.total_gift_cards, 7);
+        assert_eq!(config.breakage_policy, BREAKAGE_REFUND);
+        assert_eq!(config.quorum_bps, DEFAULT_QUORUM_BPS);
+        assert_eq!(config.bump, 254);
+        assert!(!config.is_paused(PAUSE_REDEMPTION, 0));
+    }
+
+    #[test]
+    fn legacy_referral_decodes_after_zero_extension() {
+        let owner = Pubkey::new_unique();
+        let legacy = LegacyReferral {
+            owner,
+            total_earned: 1_000,
+            referral_count: 3,
+            created_at: 1_700_000_000,
+            bump: 253,
+        };
+
+        // migrate_referral only reallocates, zero-filling the new space
+        let mut data = legacy.try_to_vec().unwrap();
+        data.resize(std::mem::size_of::<Referral>(), 0);
+
+        let referral = Referral::deserialize(&mut &data[..]).unwrap();
+        assert_eq!(referral.owner, owner);
+        assert_eq!(referral.total_earned, 1_000);
+        assert_eq!(referral.referral_count, 3);
+        assert_eq!(referral.bump, 253);
+        assert_eq!(referral.unclaimed_earnings, 0);
+        assert_eq!(referral.total_claimed, 0);
+        assert_eq!(referral.referred_by, None);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,221 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs::File;
+use std::process;
+
+// One CSV row; the leaf index is the row's position in the file
+#[derive(Deserialize)]
+struct Row {
+    recipient: String,
+    amount: u64,
+}
+
+#[derive(Serialize)]
+struct Claim {
+    index: u32,
+    recipient: String,
+    amount: u64,
+    proof: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Output {
+    merkle_root: String,
+    num_leaves: u32,
+    total_amount: u64,
+    claims: Vec<Claim>,
+}
+
+// Must match `campaign_leaf` in the program's claim_campaign instruction
+fn leaf_hash(index: u32, recipient: &[u8; 32], amount: u64) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0u8]);
+    hasher.update(index.to_le_bytes());
+    hasher.update(recipient);
+    hasher.update(amount.to_le_bytes());
+    hasher.finalize().into()
+}
+
+// Must match `verify_proof` in the program: pairs are hashed in sorted order
+fn node_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if a <= b { (a, b) } else { (b, a) };
+    let mut hasher = Sha256::new();
+    hasher.update([1u8]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+// Build every level of the tree, leaves first; an odd node is carried up unchanged
+fn build_levels(leaves: Vec<[u8; 32]>) -> Vec<Vec<[u8; 32]>> {
+    let mut levels = vec![leaves];
+    while levels.last().unwrap().len() > 1 {
+        let level = levels.last().unwrap();
+        let next = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] => node_hash(a, b),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+        levels.push(next);
+    }
+    levels
+}
+
+fn proof_for(levels: &[Vec<[u8; 32]>], mut index: usize) -> Vec<[u8; 32]> {
+    let mut proof = Vec::new();
+    for level in &levels[..levels.len() - 1] {
+        let sibling = index ^ 1;
+        if sibling < level.len() {
+            proof.push(level[sibling]);
+        }
+        index /= 2;
+    }
+    proof
+}
+
+fn parse_pubkey(value: &str) -> Result<[u8; 32], String> {
+    let bytes = bs58::decode(value.trim())
+        .into_vec()
+        .map_err(|e| format!("invalid recipient {}: {}", value, e))?;
+    bytes
+        .try_into()
+        .map_err(|_| format!("invalid recipient {}: not 32 bytes", value))
+}
+
+fn to_hex(bytes: &[u8; 32]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn run(input: &str, output: &str) -> Result<(), String> {
+    let mut reader = csv::Reader::from_path(input).map_err(|e| e.to_string())?;
+
+    let mut rows = Vec::new();
+    for row in reader.deserialize::<Row>() {
+        let row = row.map_err(|e| e.to_string())?;
+        let recipient = parse_pubkey(&row.recipient)?;
+        rows.push((row.recipient.trim().to_string(), recipient, row.amount));
+    }
+    if rows.is_empty() {
+        return Err("no recipients in input".to_string());
+    }
+
+    let leaves = rows
+        .iter()
+        .enumerate()
+        .map(|(i, (_, recipient, amount))| leaf_hash(i as u32, recipient, *amount))
+        .collect();
+    let levels = build_levels(leaves);
+    let root = levels.last().unwrap()[0];
+
+    let mut total_amount: u64 = 0;
+    let mut claims = Vec::with_capacity(rows.len());
+    for (i, (recipient, _, amount)) in rows.into_iter().enumerate() {
+        total_amount = total_amount
+            .checked_add(amount)
+            .ok_or("total amount overflows u64")?;
+        claims.push(Claim {
+            index: i as u32,
+            recipient,
+            amount,
+            proof: proof_for(&levels, i).iter().map(to_hex).collect(),
+        });
+    }
+
+    let output_data = Output {
+        merkle_root: to_hex(&root),
+        num_leaves: claims.len() as u32,
+        total_amount,
+        claims,
+    };
+    let file = File::create(output).map_err(|e| e.to_string())?;
+    serde_json::to_writer_pretty(file, &output_data).map_err(|e| e.to_string())?;
+
+    println!("Merkle root: {}", output_data.merkle_root);
+    println!("Leaves: {}, total amount: {}", output_data.num_leaves, output_data.total_amount);
+
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        eprintln!("Usage: campaign-merkle <recipients.csv> <output.json>");
+        eprintln!("The CSV needs `recipient` (base58 pubkey) and `amount` (lamports) columns");
+        process::exit(1);
+    }
+
+    if let Err(e) = run(&args[1], &args[2]) {
+        eprintln!("Error: {}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Mirrors `verify_proof` in the program's claim_campaign instruction
+    fn verify(proof: &[[u8; 32]], root: &[u8; 32], leaf: [u8; 32]) -> bool {
+        proof.iter().fold(leaf, |node, sibling| node_hash(&node, sibling)) == *root
+    }
+
+    fn leaves(count: u32) -> Vec<[u8; 32]> {
+        (0..count)
+            .map(|i| leaf_hash(i, &[i as u8 + 1; 32], 1_000 * (i as u64 + 1)))
+            .collect()
+    }
+
+    #[test]
+    fn every_proof_verifies() {
+        for count in 1..=9 {
+            let leaves = leaves(count);
+            let levels = build_levels(leaves.clone());
+            let root = levels.last().unwrap()[0];
+            for (i, leaf) in leaves.iter().enumerate() {
+                assert!(verify(&proof_for(&levels, i), &root, *leaf), "leaf {} of {}", i, count);
+            }
+        }
+    }
+
+    #[test]
+    fn proof_rejects_other_leaves() {
+        let leaves = leaves(5);
+        let levels = build_levels(leaves.clone());
+        let root = levels.last().unwrap()[0];
+        let proof = proof_for(&levels, 2);
+
+        // Same recipient with a different amount or index
+        assert!(!verify(&proof, &root, leaf_hash(2, &[3; 32], 3_001)));
+        assert!(!verify(&proof, &root, leaf_hash(3, &[3; 32], 3_000)));
+        assert!(!verify(&proof, &root, leaves[1]));
+    }
+
+    #[test]
+    fn single_leaf_is_the_root() {
+        let leaves = leaves(1);
+        let levels = build_levels(leaves.clone());
+        assert_eq!(levels.last().unwrap()[0], leaves[0]);
+        assert!(proof_for(&levels, 0).is_empty());
+    }
+
+    #[test]
+    fn root_matches_program_vector() {
+        // Pinned in the program's claim_campaign tests so both sides hash the same way
+        let levels = build_levels(leaves(3));
+        assert_eq!(
+            to_hex(&levels.last().unwrap()[0]),
+            "725419e7fa1c304ae129f03940566ccad63f97f11d8c8394ebed0ce82af2fe80"
+        );
+    }
+
+    #[test]
+    fn parse_pubkey_rejects_bad_input() {
+        assert_eq!(parse_pubkey(&bs58::encode([7u8; 32]).into_string()), Ok([7u8; 32]));
+        assert!(parse_pubkey("not-base58!").is_err());
+        assert!(parse_pubkey(&bs58::encode([7u8; 31]).into_string()).is_err());
+    }
+}
@@ -0,0 +1,190 @@
+//! Encrypted gift card messages.
+//!
+//! Messages are encrypted to the recipient's wallet (ed25519) key, converted to
+//! its x25519 form. Each message uses a fresh ephemeral x25519 key, so the
+//! derived ChaCha20-Poly1305 key is single-use and the nonce can be fixed.
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use rand_core::OsRng;
+use sha2::{Digest, Sha256, Sha512};
+use thiserror::Error;
+use x25519_dalek::{EphemeralSecret, PublicKey, StaticSecret};
+
+/// Scheme tag stored on the card for this encryption scheme
+pub const SCHEME_X25519_CHACHA20POLY1305: u8 = 1;
+
+/// Largest ciphertext the program accepts per card
+pub const MAX_CIPHERTEXT_LEN: usize = 4096;
+
+/// Ciphertext chunk size that fits in a single `append_encrypted_message` transaction
+pub const CHUNK_LEN: usize = 768;
+
+const KDF_DOMAIN: &[u8] = b"gift-protocol/message/v1";
+
+/// Error types for message encryption
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum MessageCryptoError {
+    #[error("Invalid recipient public key")]
+    InvalidPublicKey,
+
+    #[error("Unsupported encryption scheme {0}")]
+    UnsupportedScheme(u8),
+
+    #[error("Message too long")]
+    MessageTooLong,
+
+    #[error("Decryption failed")]
+    DecryptionFailed,
+}
+
+/// Encrypted message as stored on a gift card
+#[derive(Clone, Debug, PartialEq)]
+pub struct EncryptedMessage {
+    pub scheme: u8,
+    pub ephemeral_pubkey: [u8; 32],
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedMessage {
+    /// Split the ciphertext into chunks for successive `append_encrypted_message` calls
+    pub fn chunks(&self) -> impl Iterator<Item = &[u8]> {
+        self.ciphertext.chunks(CHUNK_LEN)
+    }
+}
+
+/// Convert an ed25519 wallet public key to its x25519 form
+pub fn x25519_public_key(ed25519_pubkey: &[u8; 32]) -> Result<PublicKey, MessageCryptoError> {
+    let point = CompressedEdwardsY(*ed25519_pubkey)
+        .decompress()
+        .ok_or(MessageCryptoError::InvalidPublicKey)?;
+    Ok(PublicKey::from(point.to_montgomery().to_bytes()))
+}
+
+/// Convert an ed25519 wallet secret (the 32-byte seed) to its x25519 form
+pub fn x25519_secret_key(ed25519_seed: &[u8; 32]) -> StaticSecret {
+    let hash = Sha512::digest(ed25519_seed);
+    let mut scalar = [0u8; 32];
+    scalar.copy_from_slice(&hash[..32]);
+    // Clamping is applied by x25519-dalek
+    StaticSecret::from(scalar)
+}
+
+fn derive_key(shared_secret: &[u8; 32], ephemeral: &PublicKey, recipient: &PublicKey) -> Key {
+    let mut hasher = Sha256::new();
+    hasher.update(KDF_DOMAIN);
+    hasher.update(shared_secret);
+    hasher.update(ephemeral.as_bytes());
+    hasher.update(recipient.as_bytes());
+    let digest: [u8; 32] = hasher.finalize().into();
+    Key::from(digest)
+}
+
+/// Encrypt a message to the recipient's wallet public key
+pub fn encrypt(recipient_ed25519: &[u8; 32], plaintext: &[u8]) -> Result<EncryptedMessage, MessageCryptoError> {
+    let recipient = x25519_public_key(recipient_ed25519)?;
+    let ephemeral_secret = EphemeralSecret::random_from_rng(OsRng);
+    let ephemeral = PublicKey::from(&ephemeral_secret);
+    let shared_secret = ephemeral_secret.diffie_hellman(&recipient);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(shared_secret.as_bytes(), &ephemeral, &recipient));
+    let ciphertext = cipher
+        .encrypt(&Nonce::default(), plaintext)
+        .map_err(|_| MessageCryptoError::MessageTooLong)?;
+    if ciphertext.len() > MAX_CIPHERTEXT_LEN {
+        return Err(MessageCryptoError::MessageTooLong);
+    }
+
+    Ok(EncryptedMessage {
+        scheme: SCHEME_X25519_CHACHA20POLY1305,
+        ephemeral_pubkey: ephemeral.to_bytes(),
+        ciphertext,
+    })
+}
+
+/// Decrypt a card message with the recipient's wallet secret (the 32-byte ed25519 seed)
+pub fn decrypt(recipient_ed25519_seed: &[u8; 32], message: &EncryptedMessage) -> Result<Vec<u8>, MessageCryptoError> {
+    if message.scheme != SCHEME_X25519_CHACHA20POLY1305 {
+        return Err(MessageCryptoError::UnsupportedScheme(message.scheme));
+    }
+
+    let secret = x25519_secret_key(recipient_ed25519_seed);
+    let recipient = PublicKey::from(&secret);
+    let ephemeral = PublicKey::from(message.ephemeral_pubkey);
+    let shared_secret = secret.diffie_hellman(&ephemeral);
+
+    let cipher = ChaCha20Poly1305::new(&derive_key(shared_secret.as_bytes(), &ephemeral, &recipient));
+    cipher
+        .decrypt(&Nonce::default(), message.ciphertext.as_slice())
+        .map_err(|_| MessageCryptoError::DecryptionFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::EdwardsPoint;
+
+    /// Wallet public key for an ed25519 seed, as a keypair would derive it
+    fn ed25519_pubkey(seed: &[u8; 32]) -> [u8; 32] {
+        let hash = Sha512::digest(seed);
+        let mut scalar = [0u8; 32];
+        scalar.copy_from_slice(&hash[..32]);
+        EdwardsPoint::mul_base_clamped(scalar).compress().to_bytes()
+    }
+
+    #[test]
+    fn round_trip() {
+        let seed = [7u8; 32];
+        let plaintext = b"Happy birthday! Dinner is on me.";
+
+        let message = encrypt(&ed25519_pubkey(&seed), plaintext).unwrap();
+        assert_eq!(message.scheme, SCHEME_X25519_CHACHA20POLY1305);
+        assert_ne!(message.ciphertext.as_slice(), plaintext.as_slice());
+
+        assert_eq!(decrypt(&seed, &message).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn round_trip_across_chunks() {
+        let seed = [9u8; 32];
+        let plaintext = vec![0x42u8; CHUNK_LEN * 2 + 5];
+
+        let message = encrypt(&ed25519_pubkey(&seed), &plaintext).unwrap();
+        let reassembled = EncryptedMessage {
+            ciphertext: message.chunks().flatten().copied().collect(),
+            ..message.clone()
+        };
+
+        assert_eq!(message.chunks().count(), 3);
+        assert_eq!(decrypt(&seed, &reassembled).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn wrong_key_fails() {
+        let message = encrypt(&ed25519_pubkey(&[1u8; 32]), b"secret").unwrap();
+        assert_eq!(decrypt(&[2u8; 32], &message), Err(MessageCryptoError::DecryptionFailed));
+    }
+
+    #[test]
+    fn tampered_ciphertext_fails() {
+        let seed = [3u8; 32];
+        let mut message = encrypt(&ed25519_pubkey(&seed), b"secret").unwrap();
+        message.ciphertext[0] ^= 1;
+        assert_eq!(decrypt(&seed, &message), Err(MessageCryptoError::DecryptionFailed));
+    }
+
+    #[test]
+    fn unknown_scheme_rejected() {
+        let seed = [4u8; 32];
+        let mut message = encrypt(&ed25519_pubkey(&seed), b"secret").unwrap();
+        message.scheme = 2;
+        assert_eq!(decrypt(&seed, &message), Err(MessageCryptoError::UnsupportedScheme(2)));
+    }
+
+    #[test]
+    fn oversized_message_rejected() {
+        let plaintext = vec![0u8; MAX_CIPHERTEXT_LEN];
+        assert_eq!(encrypt(&ed25519_pubkey(&[5u8; 32]), &plaintext), Err(MessageCryptoError::MessageTooLong));
+    }
+}